@@ -0,0 +1,56 @@
+//! Compare the desync reports of two peers and print where they first diverge.
+//!
+//! `cargo run --bin desync_diff -- desync_player0_frame42.ron desync_player1_frame42.ron`
+
+use dungeon_crawl_p2p::startup::DesyncReport;
+use std::{path::Path, process::ExitCode};
+
+fn main() -> ExitCode {
+    let paths = std::env::args().skip(1).collect::<Vec<_>>();
+    let [first, second] = paths.as_slice() else {
+        eprintln!("Usage: desync_diff <first report> <second report>");
+        return ExitCode::FAILURE;
+    };
+
+    let (first, second) = match (load(first), load(second)) {
+        (Ok(first), Ok(second)) => (first, second),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    for (name, report) in [("first", &first), ("second", &second)] {
+        println!(
+            "{name}: player {} frame {} checksums {:X}/{:X} random counter {}",
+            report.player_id,
+            report.frame,
+            report.local_checksum,
+            report.remote_checksum,
+            report
+                .random_counter
+                .map_or("(unavailable)".to_string(), |c| format!("{c:X}"))
+        );
+    }
+
+    if first.frame != second.frame {
+        println!("Warning: the reports are for different frames");
+    }
+    if first.random_counter != second.random_counter {
+        println!("Random counters differ");
+    }
+
+    match first.diff(&second) {
+        Some(diff) => println!("{diff}"),
+        None if first.entities.is_empty() => {
+            println!("No entity snapshots to compare. The frame was too old to report.")
+        }
+        None => println!("No differences in the rollback entities"),
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn load(path: &str) -> Result<DesyncReport, String> {
+    DesyncReport::load(Path::new(path))
+}
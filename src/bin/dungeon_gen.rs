@@ -0,0 +1,94 @@
+//! Generate a dungeon level without running the game and print it as text, or
+//! save it to a file that can be parsed back into a `DungeonMap`.
+//!
+//! `cargo run --bin dungeon_gen -- --seed 42 --builder Bsp --players 2 --out level.txt`
+//!
+//! The builder is picked by weight from `assets/builders.ron` unless one is
+//! named, and a vault from `assets/prefabs.ron` is stamped in, as in the game.
+
+use bevy::scene::ron;
+use dungeon_crawl_p2p::{
+    dungeon::{build_dungeon, BuilderData, PrefabData},
+    prelude::*,
+};
+use serde::de::DeserializeOwned;
+use std::{fs, path::Path, process::ExitCode};
+
+const USAGE: &str = "Usage: dungeon_gen [--seed <seed>] [--builder <name>] [--players <count>] \
+    [--level <level>] [--out <file>]";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), String> {
+    let mut builder = None;
+    let mut level = 1;
+    let mut num_players = 1;
+    let mut out = None;
+    let mut seed = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(USAGE)?;
+        match arg.as_str() {
+            "--builder" => builder = Some(value),
+            "--level" => level = parse(&arg, &value)?,
+            "--out" => out = Some(value),
+            "--players" => num_players = parse(&arg, &value)?,
+            "--seed" => seed = Some(parse(&arg, &value)?),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let mut builder_data: BuilderData = load("assets/builders.ron")?;
+    if let Some(name) = &builder {
+        builder_data
+            .builders
+            .retain(|b| b.builder.name().eq_ignore_ascii_case(name));
+        let Some(first) = builder_data.builders.first_mut() else {
+            return Err(format!("No builder named '{name}' in assets/builders.ron"));
+        };
+        first.weight = first.weight.max(1);
+    }
+    let prefab_data: PrefabData = load("assets/prefabs.ron")?;
+
+    let mut rng = seed.map_or_else(RandomGenerator::new, RandomGenerator::from_seed);
+    let dungeon = build_dungeon(&builder_data, &prefab_data, num_players, level, &mut rng);
+    eprintln!(
+        "Seed {} level {level} with {num_players} player(s), {}x{} tiles",
+        rng.seed,
+        dungeon.size().width,
+        dungeon.size().height
+    );
+
+    match out {
+        Some(path) => {
+            fs::write(&path, dungeon.to_string())
+                .map_err(|e| format!("Unable to write map {path}: {e}"))?;
+            eprintln!("Saved {path}");
+        }
+        None => print!("{dungeon}"),
+    }
+
+    Ok(())
+}
+
+fn load<T: DeserializeOwned>(path: &str) -> Result<T, String> {
+    let contents =
+        fs::read_to_string(Path::new(path)).map_err(|e| format!("Unable to read {path}: {e}"))?;
+
+    ron::from_str(&contents).map_err(|e| format!("Invalid {path}: {e}"))
+}
+
+fn parse<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {option} '{value}'"))
+}
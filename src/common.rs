@@ -1,9 +1,12 @@
 mod add_core_systems;
 mod debug_ggrs;
 mod dungeon_data;
+mod dungeon_theme;
 mod events;
+mod pending_change;
 mod plugin;
 mod random_generator;
+mod sprite_assets;
 
 pub mod fov {
     mod components;
@@ -31,8 +34,11 @@ pub mod health {
     pub use plugin::HealthPlugin;
 }
 
-pub use add_core_systems::add_core_systems;
+pub use add_core_systems::{add_core_systems, add_game_systems};
 pub use dungeon_data::{DungeonAssets, DungeonData};
+pub use dungeon_theme::{DungeonTheme, ThemeData};
 pub use events::{DesyncEvent, SnapshotStateEvent};
+pub use pending_change::{Change, PendingChange};
 pub use plugin::CommonPlugin;
 pub use random_generator::{RandomCounter, RandomGenerator};
+pub use sprite_assets::{deserialize_sprite, SpriteAssets};
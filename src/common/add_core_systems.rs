@@ -1,15 +1,37 @@
-use crate::prelude::*;
+use super::PendingChange;
+use crate::{
+    game_states::{GameOverReport, Pause, Winner},
+    prelude::*,
+};
 use bevy_ggrs::GgrsSchedule;
 
-/// Add core [`GameState::InGame`] systems to the Update or GgrsSchedule schedule
-/// (depending on [`GameMode`]). This is a helper function used by various
-/// module plugins.
+/// Add core [`GameState::InGame`] systems to the GgrsSchedule. They freeze
+/// while the game is paused or a [`PendingChange`] waits to be carried out,
+/// and once the game has ended (before GGRS leaves the state).
+/// This is a helper function used by various module plugins.
 pub fn add_core_systems(app: &mut App, systems: impl IntoSystemConfigs<()>) {
-    let core_systems = systems.run_if(in_state(GameState::InGame));
+    add_game_systems(
+        app,
+        systems.run_if(
+            in_state(GameState::InGame)
+                .and(Pause::running)
+                .and(PendingChange::none)
+                .and(not(resource_exists::<GameOverReport>))
+                .and(not(resource_exists::<Winner>)),
+        ),
+    );
+}
+
+/// Add systems to the GgrsSchedule without restricting them to
+/// [`GameState::InGame`]. Use for systems that must stay in sync among peers in
+/// other states (e.g. restart).
+pub fn add_game_systems(app: &mut App, systems: impl IntoSystemConfigs<()>) {
+    app.add_systems(GgrsSchedule, systems);
+}
 
-    if game_mode(GameMode::SinglePlayer) {
-        app.add_systems(Update, core_systems);
-    } else {
-        app.add_systems(GgrsSchedule, core_systems);
-    }
+/// GGRS runs the GgrsSchedule once a session starts (in the P2P or sync test
+/// [`GameMode`]). In single player mode there is no session, so we run it every
+/// frame as part of the Update schedule instead.
+pub fn run_single_player_schedule(world: &mut World) {
+    world.run_schedule(GgrsSchedule);
 }
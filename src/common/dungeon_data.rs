@@ -1,12 +1,23 @@
-use crate::{items::MagicItemTemplate, monsters::MonsterTemplate};
+use super::ThemeData;
+use crate::{
+    dungeon::{BuilderData, PrefabData},
+    items::MagicItemTemplate,
+    monsters::MonsterTemplate,
+};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use serde::Deserialize;
 
 #[derive(AssetCollection, Resource)]
 pub struct DungeonAssets {
+    #[asset(path = "builders.ron")]
+    pub builders: Handle<BuilderData>,
     #[asset(path = "dungeon.ron")]
     pub data: Handle<DungeonData>,
+    #[asset(path = "prefabs.ron")]
+    pub prefabs: Handle<PrefabData>,
+    #[asset(path = "themes.ron")]
+    pub themes: Handle<ThemeData>,
 }
 
 #[derive(Asset, Deserialize, TypePath)]
@@ -0,0 +1,57 @@
+use crate::{items::MagicItemKind, monsters::Monster};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+#[derive(Asset, Deserialize, TypePath)]
+pub struct ThemeData {
+    pub themes: Vec<DungeonTheme>,
+}
+
+/// Look and inhabitants of a dungeon level. Each level picks one of the themes
+/// in `themes.ron` at random.
+#[derive(Clone, Debug, Deserialize, Resource)]
+pub struct DungeonTheme {
+    /// Background color of the unexplored darkness
+    ambient: Srgba,
+    pub floor: TileColors,
+    /// Kinds of items that may spawn. Any item may spawn if empty.
+    #[serde(default)]
+    pub items: Vec<MagicItemKind>,
+    /// Monsters that may spawn. Any monster may spawn if empty.
+    #[serde(default)]
+    pub monsters: Vec<Monster>,
+    pub name: String,
+    pub wall: TileColors,
+}
+
+impl DungeonTheme {
+    pub fn allows_item(&self, kind: MagicItemKind) -> bool {
+        self.items.is_empty() || self.items.contains(&kind)
+    }
+
+    pub fn allows_monster(&self, monster: Monster) -> bool {
+        self.monsters.is_empty() || self.monsters.contains(&monster)
+    }
+
+    pub fn ambient(&self) -> Color {
+        self.ambient.into()
+    }
+}
+
+/// Tile colors when out of the local player's FOV (but already seen) and when
+/// illuminated by being in it.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TileColors {
+    color: Srgba,
+    illuminated: Srgba,
+}
+
+impl TileColors {
+    pub fn color(&self) -> Color {
+        self.color.into()
+    }
+
+    pub fn illuminated(&self) -> Color {
+        self.illuminated.into()
+    }
+}
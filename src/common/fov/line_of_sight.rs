@@ -1,16 +1,16 @@
 use super::FovRadius;
 use crate::dungeon::WallTile;
 use bevy::prelude::*;
-use bevy::utils::hashbrown::HashSet;
+use bevy::utils::hashbrown::HashMap;
 
-pub type WallQuery<'w, 's, 't> = Query<'w, 's, &'t Transform, With<WallTile>>;
+pub type WallQuery<'w, 's, 't> = Query<'w, 's, (&'t Transform, Entity), With<WallTile>>;
 
 /// Use [Bresenham's line algorithm](https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm)
 /// to determine if a wall blocks the line of sight to the given floor tile.
 pub struct BresenhamLineOfSight {
     pub pos: IVec2,
     radius_sq: i32,
-    wall_set: HashSet<IVec2>,
+    wall_map: HashMap<IVec2, Entity>,
 }
 
 impl BresenhamLineOfSight {
@@ -18,7 +18,7 @@ impl BresenhamLineOfSight {
         Self {
             pos: entity_pos,
             radius_sq: (radius * radius) as i32,
-            wall_set: Self::create_wall_set(walls),
+            wall_map: Self::create_wall_map(walls),
         }
     }
 
@@ -39,7 +39,7 @@ impl BresenhamLineOfSight {
         let mut error_term = x_distance - y_distance;
 
         while !(x == floor_x && y == floor_y) {
-            if self.wall_set.contains(&IVec2::new(x, y)) {
+            if self.wall_map.contains_key(&IVec2::new(x, y)) {
                 return false; // wall obstructs line of sight
             }
 
@@ -59,10 +59,15 @@ impl BresenhamLineOfSight {
         true // clear line of sight
     }
 
-    fn create_wall_set(walls: &WallQuery) -> HashSet<IVec2> {
+    /// The wall tile at the given position, if any.
+    pub fn wall(&self, pos: &IVec2) -> Option<Entity> {
+        self.wall_map.get(pos).copied()
+    }
+
+    fn create_wall_map(walls: &WallQuery) -> HashMap<IVec2, Entity> {
         walls
             .iter()
-            .map(|t| t.translation.truncate().as_ivec2())
+            .map(|(t, wall)| (t.translation.truncate().as_ivec2(), wall))
             .collect()
     }
 
@@ -17,9 +17,7 @@ impl Plugin for FovPlugin {
 
         common::add_core_systems(app, core_systems);
 
-        if !game_mode(GameMode::SinglePlayer) {
-            app.rollback_component_with_clone::<FieldOfView>();
-        }
+        app.rollback_component_with_clone::<FieldOfView>();
 
         app.add_plugins(FovEventsPlugin);
     }
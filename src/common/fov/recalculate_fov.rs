@@ -5,7 +5,8 @@ use super::{
     FovRadius, FovTileMap, RecalculateFovEvent,
 };
 use crate::{
-    dungeon::{FloorQuery, Illuminator, PlayerQuery},
+    common::DungeonTheme,
+    dungeon::{Illuminator, PlayerQuery, TileQuery},
     player::LocalPlayer,
 };
 use bevy::{prelude::*, utils::hashbrown::HashSet};
@@ -13,16 +14,18 @@ use bevy_ggrs::LocalPlayers;
 
 /// Recalculate the field of view for the entity that triggered the event.
 /// If the event was triggered by the local player moving, illuminate or darken
-/// the floor tiles based on the new FOV, and check the visibility of all other
-/// relevant entities. Otherwise, we need only update the visibility of the entity
-/// that moved and there's no change in floor tile illumination.
+/// the floor and wall tiles based on the new FOV, and check the visibility of all
+/// other relevant entities. Otherwise, we need only update the visibility of the
+/// entity that moved and there's no change in tile illumination.
+#[allow(clippy::too_many_arguments)]
 pub fn recalculate_fov(
     mut fov_query: FovQuery,
     mut recalculate_events: EventReader<RecalculateFovEvent>,
-    mut floor: FloorQuery,
+    mut tiles: TileQuery,
     mut entities: VisibilityQuery,
     local_players: Res<LocalPlayers>,
     players: PlayerQuery,
+    theme: Res<DungeonTheme>,
     walls: WallQuery,
 ) {
     for event in recalculate_events.read() {
@@ -31,11 +34,12 @@ pub fn recalculate_fov(
             .map(|(fov, _)| fov)
             .expect("Inconceivable!");
 
-        let revised_fov = calculate_fov(event.pos, fov.radius, &floor, &walls);
+        let is_player = players.contains(event.entity);
+        let revised_fov = calculate_fov(event.pos, fov.radius, &tiles, &walls, is_player);
         let mover_is_local_player = is_local_player(event.entity, &local_players, &players);
 
         if mover_is_local_player {
-            Illuminator::new(&fov.visible_tiles).illuminate(&mut floor, &revised_fov);
+            Illuminator::new(&fov.visible_tiles, &theme).illuminate(&mut tiles, &revised_fov);
         }
 
         fov.visible_tiles = revised_fov.clone();
@@ -52,19 +56,43 @@ pub fn recalculate_fov(
     }
 }
 
+/// The FOV holds the floor tiles in line of sight. Player FOVs also hold the
+/// walls bordering them (including diagonally), so room shapes are revealed as
+/// the player explores. Monsters have no use for the walls.
 fn calculate_fov(
     pos: IVec2,
     radius: FovRadius,
-    floor: &FloorQuery,
+    tiles: &TileQuery,
     walls: &WallQuery,
+    is_player: bool,
 ) -> FovTileMap {
     let viewer = BresenhamLineOfSight::new(pos, radius, &walls);
 
-    floor
+    let mut fov: FovTileMap = tiles
         .iter()
+        .filter(|(_, _, _, is_wall, ..)| !is_wall)
         .map(|(t, tile, ..)| (t.translation.truncate().as_ivec2(), tile))
         .filter(|(floor_pos, _)| viewer.can_see(floor_pos))
-        .collect()
+        .collect();
+
+    if is_player {
+        let bordering_walls: Vec<(IVec2, Entity)> = fov
+            .keys()
+            .flat_map(|floor_pos| neighbors(*floor_pos))
+            .filter_map(|pos| viewer.wall(&pos).map(|wall| (pos, wall)))
+            .collect();
+        fov.extend(bordering_walls);
+    }
+
+    fov
+}
+
+/// The 8 tiles around the position
+fn neighbors(pos: IVec2) -> impl Iterator<Item = IVec2> {
+    (-1..=1)
+        .flat_map(|x| (-1..=1).map(move |y| IVec2::new(x, y)))
+        .filter(|offset| *offset != IVec2::ZERO)
+        .map(move |offset| pos + offset)
 }
 
 fn is_local_player(entity: Entity, local_players: &LocalPlayers, players: &PlayerQuery) -> bool {
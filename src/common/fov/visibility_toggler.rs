@@ -1,8 +1,8 @@
-use crate::dungeon::FloorTile;
+use crate::dungeon::{FloorTile, WallTile};
 use bevy::{prelude::*, utils::hashbrown::HashSet};
 
 pub type VisibilityQuery<'w, 's, 't, 'v> =
-    Query<'w, 's, (&'t Transform, &'v mut Visibility), Without<FloorTile>>;
+    Query<'w, 's, (&'t Transform, &'v mut Visibility), (Without<FloorTile>, Without<WallTile>)>;
 
 /// Toggle the visibility of entities based on their position in the player's FOV.
 pub struct VisibilityToggler {
@@ -14,13 +14,11 @@ impl Plugin for HealthPlugin {
                 .before(MonstersCoreSet),
         );
 
-        if !game_mode(GameMode::SinglePlayer) {
-            app.rollback_component_with_copy::<Damage>()
-                .checksum_component_with_hash::<Damage>()
-                .rollback_component_with_clone::<Healing>()
-                .rollback_component_with_copy::<Health>()
-                .checksum_component_with_hash::<Health>();
-        }
+        app.rollback_component_with_copy::<Damage>()
+            .checksum_component_with_hash::<Damage>()
+            .rollback_component_with_clone::<Healing>()
+            .rollback_component_with_copy::<Health>()
+            .checksum_component_with_hash::<Health>();
 
         app.add_plugins(HealthEventsPlugin);
     }
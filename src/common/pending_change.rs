@@ -0,0 +1,77 @@
+use crate::{game_states::GameOverReport, player::PlayerId, prelude::*};
+
+/// A change that replaces state GGRS does not roll back, such as the dungeon
+/// tiles, the [`DungeonMap`] or the HUD. It is carried out
+/// [`config::GGRS_MAX_PREDICTION`] frames after it was requested, when GGRS can
+/// no longer roll back the frame of the request, so all peers carry it out on
+/// the same frame and none ever has to undo it. The core systems freeze in the
+/// meantime, so frames re-simulated after a rollback do not depend on whether
+/// the change was already carried out.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Resource)]
+pub struct PendingChange {
+    change: Option<Change>,
+    frames_left: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Change {
+    /// Descend to the given dungeon level
+    NextLevel(usize),
+    Restart,
+    /// End the game with the death of a player
+    GameOver(GameOverReport),
+    /// End the game, won by the given player
+    Win(PlayerId),
+}
+
+impl PendingChange {
+    pub fn change(&self) -> Option<Change> {
+        self.change
+    }
+
+    /// Request a change, replacing any pending one.
+    pub fn request(&mut self, change: Change) {
+        self.change = Some(change);
+        self.frames_left = config::GGRS_MAX_PREDICTION;
+    }
+
+    /// Count down a frame. Returns true, clearing the change, once it is due.
+    pub fn tick(&mut self) -> bool {
+        if self.frames_left > 0 {
+            self.frames_left -= 1;
+            return false;
+        }
+
+        *self = Self::default();
+        true
+    }
+
+    /// Run condition for systems that freeze while a change is pending.
+    pub fn none(pending: Res<PendingChange>) -> bool {
+        pending.change.is_none()
+    }
+
+    /// Run condition for systems that carry out a pending restart.
+    pub fn restart(pending: Res<PendingChange>) -> bool {
+        pending.change == Some(Change::Restart)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn due_after_max_prediction() {
+        let mut pending = PendingChange::default();
+
+        pending.request(Change::NextLevel(2));
+
+        for _ in 0..config::GGRS_MAX_PREDICTION {
+            assert!(!pending.tick());
+        }
+        assert_eq!(pending.change(), Some(Change::NextLevel(2)));
+        assert!(pending.tick());
+        assert_eq!(pending.change(), None);
+    }
+}
@@ -1,15 +1,31 @@
-use super::{debug_ggrs::debug_ggrs, events::CommonEventsPlugin, fov::FovCoreSet};
+use super::{
+    add_core_systems::run_single_player_schedule, debug_ggrs::debug_ggrs,
+    events::CommonEventsPlugin, fov::FovCoreSet, PendingChange,
+};
 use crate::prelude::*;
-use bevy_ggrs::GgrsSchedule;
+use bevy_ggrs::{GgrsApp, GgrsSchedule};
 
 pub struct CommonPlugin;
 
 impl Plugin for CommonPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(CommonEventsPlugin);
+        app.add_plugins(CommonEventsPlugin)
+            .init_resource::<PendingChange>()
+            .add_systems(
+                Update,
+                run_single_player_schedule.run_if(game_mode(GameMode::SinglePlayer)),
+            );
 
-        if config::GGRS_DEBUG && !game_mode(GameMode::SinglePlayer) {
-            app.add_systems(GgrsSchedule, debug_ggrs.after(FovCoreSet));
+        app.rollback_resource_with_copy::<PendingChange>()
+            .checksum_resource_with_hash::<PendingChange>();
+
+        if config::GGRS_DEBUG {
+            app.add_systems(
+                GgrsSchedule,
+                debug_ggrs
+                    .after(FovCoreSet)
+                    .run_if(not(game_mode(GameMode::SinglePlayer))),
+            );
         }
     }
 }
@@ -6,19 +6,26 @@ use std::hash::{Hash, Hasher};
 use std::ops::Range;
 
 /// We seed the random number generator so that in a multi-player game the random
-/// numbers generated are exactly the same for both players. This means we do
+/// numbers generated are exactly the same for all players. This means we do
 /// not have to keep random stuff in sync as it is deterministic.
 #[derive(Clone, Debug, Resource)]
 pub struct RandomGenerator {
     pub counter: RandomCounter,
     rng: Xoshiro256PlusPlus,
+    /// Seed of the current sequence, recorded so a game can be replayed
+    pub seed: u64,
 }
 
 impl RandomGenerator {
     pub fn new() -> Self {
+        Self::from_seed(thread_rng().next_u64())
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
         Self {
             counter: 0,
-            rng: Xoshiro256PlusPlus::seed_from_u64(thread_rng().next_u64()),
+            rng: Xoshiro256PlusPlus::seed_from_u64(seed),
+            seed,
         }
     }
 
@@ -34,10 +41,13 @@ impl RandomGenerator {
             .map(xor)
             .fold(socket_id, |acc, id| acc ^ id);
 
-        Self {
-            counter: 0,
-            rng: Xoshiro256PlusPlus::seed_from_u64(seed),
-        }
+        Self::from_seed(seed)
+    }
+
+    /// Seed a new sequence from the current one. Since the generator is in sync,
+    /// all peers end up with the same new seed.
+    pub fn reseed(&mut self) {
+        *self = Self::from_seed(self.rng.next_u64());
     }
 
     pub fn gen_bool(&mut self, p: f64) -> bool {
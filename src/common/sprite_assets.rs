@@ -0,0 +1,83 @@
+use crate::prelude::*;
+use bevy_asset_loader::prelude::*;
+use serde::{de::Error, Deserialize, Deserializer};
+
+/// Number of glyphs in the atlas, which is 8 glyphs wide and 2 high
+pub const SPRITE_COUNT: usize = 16;
+
+/// Sprite sheet with a 16x16 pixel glyph per index. Glyphs are white so they
+/// can be tinted with the color of the entity (e.g. the monster template color).
+#[derive(AssetCollection, Resource)]
+pub struct SpriteAssets {
+    #[asset(texture_atlas_layout(tile_size_x = 16, tile_size_y = 16, columns = 8, rows = 2))]
+    pub layout: Handle<TextureAtlasLayout>,
+    #[asset(path = "sprites/atlas.png")]
+    #[asset(image(sampler(filter = nearest)))]
+    pub sheet: Handle<Image>,
+}
+
+impl SpriteAssets {
+    /// A tile-sized sprite showing the glyph at the given atlas index tinted
+    /// with the given color. Without an index, it is just a square of the color.
+    pub fn sprite(&self, index: Option<usize>, color: Color) -> Sprite {
+        let sprite = index.map_or_else(Sprite::default, |index| {
+            Sprite::from_atlas_image(
+                self.sheet.clone(),
+                TextureAtlas {
+                    index,
+                    layout: self.layout.clone(),
+                },
+            )
+        });
+
+        Sprite {
+            color,
+            custom_size: Some(Vec2::new(TILE_WIDTH, TILE_HEIGHT)),
+            ..sprite
+        }
+    }
+}
+
+/// Deserializes an optional atlas index, failing the asset load if the atlas
+/// has no glyph at that index.
+pub fn deserialize_sprite<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<usize>, D::Error> {
+    let sprite = Option::<usize>::deserialize(deserializer)?;
+
+    match sprite {
+        Some(index) if index >= SPRITE_COUNT => Err(D::Error::custom(format!(
+            "Sprite index {index} is out of the atlas (only {SPRITE_COUNT} glyphs)"
+        ))),
+        _ => Ok(sprite),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::scene::ron;
+
+    #[derive(Debug, Deserialize)]
+    struct Template {
+        #[serde(default, deserialize_with = "deserialize_sprite")]
+        sprite: Option<usize>,
+    }
+
+    #[test]
+    fn load_sprite() {
+        let template: Template = ron::from_str("(sprite: Some(15))").unwrap();
+
+        assert_eq!(template.sprite, Some(15));
+        assert_eq!(ron::from_str::<Template>("()").unwrap().sprite, None);
+    }
+
+    #[test]
+    fn sprite_out_of_atlas() {
+        let error = ron::from_str::<Template>("(sprite: Some(16))").unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("Sprite index 16 is out of the atlas"));
+    }
+}
@@ -1,26 +1,78 @@
-use crate::{
-    fov::FovRadius,
-    prelude::{TILE_HEIGHT, TILE_WIDTH},
+use crate::fov::FovRadius;
+use bevy::{
+    color::Color,
+    prelude::{Res, Resource},
+    render::view::Layer,
 };
-use bevy::{color::Color, render::view::Layer};
 use bevy_matchbox::prelude::PeerId;
+use std::str::FromStr;
 
 // Game
-#[cfg(not(target_arch = "wasm32"))]
-pub const GAME_MODE: GameMode = GameMode::SinglePlayer;
-#[cfg(target_arch = "wasm32")]
-pub const GAME_MODE: GameMode = GameMode::MultiPlayer;
-
-#[derive(Eq, PartialEq)]
+/// Chosen at runtime via a launch option or the main menu, and then inserted
+/// as a resource before entering `GameState::Startup`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Resource)]
 pub enum GameMode {
-    #[allow(dead_code)]
     GgrsSyncTest,
     MultiPlayer,
+    /// Play back a recording given by the `replay` launch option
+    Replay,
     SinglePlayer,
 }
 
-pub fn game_mode(mode: GameMode) -> bool {
-    GAME_MODE == mode
+impl FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "multi" => Ok(GameMode::MultiPlayer),
+            "single" => Ok(GameMode::SinglePlayer),
+            "synctest" => Ok(GameMode::GgrsSyncTest),
+            _ => Err(format!(
+                "Unknown game mode '{s}'. Use multi, single or synctest"
+            )),
+        }
+    }
+}
+
+/// Run condition that is true once the given [`GameMode`] has been chosen.
+pub fn game_mode(mode: GameMode) -> impl Fn(Option<Res<GameMode>>) -> bool + Clone {
+    move |current: Option<Res<GameMode>>| current.is_some_and(|current| *current == mode)
+}
+
+/// Number of players in the game. Inserted along with the [`GameMode`] and
+/// adjustable in the lobby for multiplayer games.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Resource)]
+pub struct NumPlayers(pub usize);
+
+impl NumPlayers {
+    /// Always 1 in single player mode. Otherwise the count given at launch or
+    /// [`NUM_PLAYERS`].
+    pub fn new(game_mode: GameMode, num_players: Option<usize>) -> Self {
+        match game_mode {
+            GameMode::GgrsSyncTest | GameMode::MultiPlayer | GameMode::Replay => {
+                Self(num_players.unwrap_or(NUM_PLAYERS))
+            }
+            GameMode::SinglePlayer => Self(1),
+        }
+    }
+
+    /// Parse a multiplayer player count between [`MIN_PLAYERS`] and [`MAX_PLAYERS`].
+    pub fn parse(s: &str) -> Result<usize, String> {
+        s.parse()
+            .ok()
+            .filter(|n| (MIN_PLAYERS..=MAX_PLAYERS).contains(n))
+            .ok_or_else(|| {
+                format!("Invalid player count '{s}'. Use {MIN_PLAYERS} to {MAX_PLAYERS}")
+            })
+    }
+
+    /// Add to the player count, staying between [`MIN_PLAYERS`] and [`MAX_PLAYERS`].
+    pub fn add(&mut self, delta: isize) {
+        self.0 = self
+            .0
+            .saturating_add_signed(delta)
+            .clamp(MIN_PLAYERS, MAX_PLAYERS);
+    }
 }
 
 // Camera
@@ -31,35 +83,78 @@ pub const CAMERA_SCALE: f32 = 2.;
 pub const ITEM_Z_LAYER: f32 = 80.;
 
 // Monsters
+/// Probability per frame that a monster with low health starts fleeing
+pub const MONSTER_FLEE_CHANCE: f64 = 0.1;
 pub const MONSTER_FOV_RADIUS: FovRadius = 6;
+/// Seconds a monster keeps heading to where it last saw a player
+pub const MONSTER_MEMORY_SECONDS: f32 = 4.;
 pub const MONSTER_Z_LAYER: f32 = 90.;
 /// Probability that a monster will attempt to move in a given frame.
 /// See monster_movement.
 pub const MONSTER_MOVE_CHANCE: f64 = 0.01;
+/// Probability per frame that a patrolling monster gives up on reaching its
+/// next waypoint (e.g. because it is stuck behind a wall) and heads to the next
+pub const MONSTER_PATROL_GIVE_UP_CHANCE: f64 = 0.005;
+/// Probability that a patrolling (or guard returning to its post) monster
+/// takes a step in a given frame
+pub const MONSTER_PATROL_MOVE_CHANCE: f64 = 0.1;
+/// Distance of patrol waypoints from the monster's starting position
+pub const MONSTER_PATROL_RADIUS: i32 = 4;
+/// Probability per frame that an awake resting monster with no target goes
+/// back to sleep
+pub const MONSTER_SLEEP_CHANCE: f64 = 0.001;
 pub const MONSTER_THROTTLE_SECONDS: f32 = 0.15;
+/// Probability per frame that a sleeping monster wakes up when a player is near
+pub const MONSTER_WAKE_CHANCE: f64 = 0.05;
+/// A sleeping monster may wake up when a player is within this distance
+pub const MONSTER_WAKE_RADIUS: i32 = 3;
 /// Auto save tracked monster moves when threshold is reached
 pub const MONSTER_TRACKER_AUTO_SAVE_ENABLED: bool = false;
 pub const MONSTER_TRACKER_AUTO_SAVE_THRESHOLD: usize = 100;
 
 // Player
-pub const PLAYER_0_COLOR: Color = Color::srgb(1., 1., 1.);
-pub const PLAYER_1_COLOR: Color = Color::srgb(0., 1., 0.);
-pub const PLAYER_WIDTH: f32 = TILE_WIDTH;
-pub const PLAYER_HEIGHT: f32 = TILE_HEIGHT;
+/// Color of each player's glyph, indexed by player id
+pub const PLAYER_COLORS: [Color; MAX_PLAYERS] = [
+    Color::srgb(1., 1., 1.),
+    Color::srgb(0., 1., 0.),
+    Color::srgb(0.3, 0.7, 1.),
+    Color::srgb(1., 0.6, 0.),
+];
 pub const PLAYER_FOV_RADIUS: FovRadius = 8;
 /// Amount of seconds between auto-health increments
 pub const PLAYER_HEALING_SECONDS: f32 = 10.0;
 pub const PLAYER_HEALTH_MAX: u8 = 10;
 /// Player is invincible for debugging purposes
-pub const PLAYER_IMMORTAL: bool = true;
+pub const PLAYER_IMMORTAL: bool = false;
 pub const PLAYER_MOVE_THROTTLE_SECONDS: f32 = 0.1;
+/// Index of the player glyph in the SpriteAssets atlas
+pub const PLAYER_SPRITE: usize = 3;
 pub const PLAYER_Z_LAYER: f32 = 100.;
 
 // Matchbox and GGRS
 pub const GGRS_DEBUG: bool = false;
+/// Seconds without hearing from a peer before the waiting for peer overlay shows
+pub const GGRS_DISCONNECT_NOTIFY_SECONDS: u64 = 1;
+/// Seconds without hearing from a peer before they are dropped from the game
+pub const GGRS_DISCONNECT_TIMEOUT_SECONDS: u64 = 10;
 pub const GGRS_INPUT_DELAY: usize = if GGRS_DEBUG { 0 } else { 2 };
-// pub const MATCHBOX_ROOM_URL: &str = "ws://127.0.0.1:3536/dungeon_crawl?next=2";
-pub const MATCHBOX_ROOM_URL: &str = "ws://3.147.199.67:3536/dungeon_crawl?next=2";
+/// Frames GGRS may run ahead of the confirmed inputs. It never rolls back
+/// further, so changes to state it does not roll back wait this long (see
+/// `common::PendingChange`).
+pub const GGRS_MAX_PREDICTION: usize = 8;
+/// Prefix of the room path on the signaling server, followed by the room code
+pub const MATCHBOX_ROOM_PREFIX: &str = "dungeon_crawl";
+/// Default signaling server, overridden by the `server` launch option or the
+/// `MATCHBOX_SERVER` env var. Use "ws://127.0.0.1:3536" for a local matchbox_server.
+pub const MATCHBOX_SERVER_URL: &str = "ws://3.147.199.67:3536";
+pub const MAX_PLAYERS: usize = 4;
+pub const MIN_PLAYERS: usize = 2;
+/// Default number of players in multiplayer and sync test sessions
 pub const NUM_PLAYERS: usize = 2;
+pub const REPLAY_SPEED_MAX: f32 = 16.;
+pub const REPLAY_SPEED_MIN: f32 = 0.25;
+/// Letters only, leaving out I and O which are easily mistaken for 1 and 0
+pub const ROOM_CODE_ALPHABET: &str = "ABCDEFGHJKLMNPQRSTUVWXYZ";
+pub const ROOM_CODE_LENGTH: usize = 4;
 
 pub type GgrsSessionConfig = bevy_ggrs::GgrsConfig<u8, PeerId>;
@@ -1,20 +1,35 @@
 mod components;
 mod config;
+mod descend;
+mod despawn_dungeon;
 mod dungeon_map {
     mod a_star {
         mod a_star_node;
         mod a_star_path_finder;
 
-        pub(super) use a_star_path_finder::AStarPathFinder;
+        pub use a_star_path_finder::AStarPathFinder;
 
         use super::*;
         use a_star_node::AStarNode;
     }
+    mod ascii_map;
+    mod bsp {
+        mod bsp_builder;
+        mod config;
+
+        pub use bsp_builder::BspBuilder;
+        pub use config::BspConfig;
+
+        use super::*;
+    }
+
     mod cell_automata {
         mod cell_automata_builder;
         mod cell_grower;
+        mod config;
 
         pub use cell_automata_builder::CellAutomataBuilder;
+        pub use config::CellAutomataConfig;
 
         use super::*;
         use cell_grower::CellGrower;
@@ -30,56 +45,113 @@ mod dungeon_map {
         use super::*;
     }
 
+    mod dijkstra_map;
+    mod dungeon_builder;
     mod dungeon_corner;
     mod dungeon_map;
     mod dungeon_position;
     mod dungeon_tile;
+    mod map_size;
 
     mod random_rooms {
+        mod config;
         mod random_rooms_builder;
         mod room;
 
+        pub use config::RandomRoomsConfig;
         pub use random_rooms_builder::RandomRoomsBuilder;
+        pub use room::Room;
 
         use super::*;
-        use room::Room;
     }
 
     mod tunneler;
 
+    mod vaults {
+        mod prefab;
+        mod vault_stamper;
+
+        pub use prefab::{Prefab, PrefabData};
+        pub use vault_stamper::VaultStamper;
+
+        use super::*;
+        use prefab::PrefabTile;
+    }
+
+    mod wall_kind;
+
+    mod wfc {
+        mod config;
+        mod patterns;
+        mod wave;
+        mod wfc_builder;
+
+        pub use config::WfcConfig;
+        pub use wfc_builder::WfcBuilder;
+
+        use super::*;
+        use patterns::{opposite, Patterns, DIRECTIONS};
+        use wave::Wave;
+    }
+
+    pub use a_star::AStarPathFinder;
+    pub use dijkstra_map::{DijkstraDistance, DijkstraMap};
+    pub use dungeon_builder::{BuilderConfig, BuilderData, DungeonBuilder, WeightedBuilder};
     pub use dungeon_map::DungeonMap;
     pub use dungeon_position::DungeonPosition;
+    pub use dungeon_tile::TileType;
+    pub use map_size::MapSize;
+    pub use vaults::PrefabData;
+
+    pub use bsp::BspConfig;
+    pub use cell_automata::CellAutomataConfig;
+    pub use drunkards_walk::DrunkardsWalkConfig;
+    pub use random_rooms::RandomRoomsConfig;
+    pub use wfc::WfcConfig;
 
-    pub(super) use cell_automata::CellAutomataBuilder;
-    pub(super) use drunkards_walk::{DrunkardsWalkBuilder, DrunkardsWalkConfig};
-    pub(super) use dungeon_tile::{DungeonTile, TileType};
-    pub(super) use random_rooms::RandomRoomsBuilder;
+    pub(super) use dungeon_tile::DungeonTile;
+    pub(super) use vaults::VaultStamper;
+    pub(super) use wall_kind::WallKind;
 
     use super::config::*;
-    use a_star::AStarPathFinder;
+    use bsp::BspBuilder;
+    use cell_automata::CellAutomataBuilder;
+    use drunkards_walk::DrunkardsWalkBuilder;
     use dungeon_corner::DungeonCorner;
+    use map_size::MapPos;
+    use random_rooms::{RandomRoomsBuilder, Room};
     use tunneler::Tunneler;
+    use wfc::WfcBuilder;
 }
 mod events;
 mod illuminator;
 mod plugin;
 mod reveal_map;
 mod spawn_dungeon;
+mod take_stairs;
+mod update_dijkstra_maps;
 mod zoom;
 
 pub use components::{FloorTile, WallTile};
 pub use config::{NUM_MONSTERS, TILE_HEIGHT, TILE_WIDTH, VIEWPORT_HEIGHT};
-pub use dungeon_map::{DungeonMap, DungeonPosition};
-pub use events::{RevealDungeonEvent, ZoomEvent};
-pub use illuminator::{FloorQuery, Illuminator, PlayerQuery};
+pub use dungeon_map::{
+    AStarPathFinder, BspConfig, BuilderConfig, BuilderData, CellAutomataConfig, DijkstraDistance,
+    DijkstraMap, DrunkardsWalkConfig, DungeonBuilder, DungeonMap, DungeonPosition, MapSize,
+    PrefabData, RandomRoomsConfig, TileType, WeightedBuilder, WfcConfig,
+};
+pub use events::{NextLevelEvent, RevealDungeonEvent, ZoomEvent};
+pub use illuminator::{Illuminator, PlayerQuery, TileQuery};
 pub use plugin::{DungeonCoreSet, DungeonPlugin, SpawnDungeonSet};
+pub use spawn_dungeon::build_dungeon;
 
 use components::*;
 use config::*;
-use dungeon_map::{
-    CellAutomataBuilder, DrunkardsWalkBuilder, DrunkardsWalkConfig, RandomRoomsBuilder, TileType,
-};
+use descend::descend;
+use despawn_dungeon::despawn_dungeon;
+use dungeon_map::{VaultStamper, WallKind};
 use events::*;
 use reveal_map::reveal_map;
 use spawn_dungeon::spawn_dungeon;
+use take_stairs::take_stairs;
+use update_dijkstra_maps::update_dijkstra_maps;
 use zoom::zoom;
@@ -1,8 +1,13 @@
 use bevy::color::Color;
 
 // Dungeon view
-pub const FLOOR_COLOR: Color = Color::srgb(0.3, 0.3, 0.3); // not illuminated: dark gray
-pub const FLOOR_ILLUMINATED_COLOR: Color = Color::srgb(0.7, 0.7, 0.4); // illuminated: warm yellow-gray
+/// The amulet is on the deepest level, which has no exit
+pub const DEEPEST_LEVEL: usize = 3;
+pub const EXIT_COLOR: Color = Color::srgb(0.6, 0.6, 0.6); // not illuminated: light gray
+pub const EXIT_ILLUMINATED_COLOR: Color = Color::srgb(1., 1., 1.); // illuminated: white
+pub const EXIT_SPRITE: usize = 2;
+pub const FLOOR_SPRITE: usize = 0;
+/// Default map size, unless a builder in builders.ron sets its own
 pub const MAP_WIDTH: usize = 100;
 pub const MAP_HEIGHT: usize = 52;
 pub const MAP_Z_LAYER: f32 = 10.;
@@ -14,18 +19,42 @@ pub const SAFETY_RADIUS: f32 = 10.;
 pub const TILE_WIDTH: f32 = 1.;
 pub const TILE_HEIGHT: f32 = TILE_WIDTH;
 
-pub const X_MAX: isize = (MAP_WIDTH / 2 - 1) as isize;
-pub const X_MIN: isize = -((MAP_WIDTH / 2) as isize);
-pub const Y_MAX: isize = ((MAP_HEIGHT / 2) - 1) as isize;
-pub const Y_MIN: isize = -((MAP_HEIGHT / 2) as isize);
-
 pub const VIEWPORT_HEIGHT: f32 = 20.;
+pub const WALL_CORNER_SPRITE: usize = 12;
+pub const WALL_HORIZONTAL_SPRITE: usize = 13;
+pub const WALL_PILLAR_SPRITE: usize = 15;
+pub const WALL_SPRITE: usize = 1;
+pub const WALL_VERTICAL_SPRITE: usize = 14;
+
+// Defaults of the builder settings in builders.ron
+
+// BspBuilder settings
+/// Times the map is split in two, for up to 2^depth rooms
+pub const BSP_MAX_DEPTH: usize = 5;
+/// Smallest width or height of a partition, which must fit a room and its walls
+pub const BSP_MIN_LEAF_SIZE: usize = 8;
+
+// CellAutomataBuilder settings
+pub const CELL_GENERATIONS: usize = 10;
+/// Percent chance of a tile starting out as floor
+pub const FLOOR_CHANCE: usize = 45;
 
 // DrunkardsWalkBuilder settings
 pub const PERCENT_FLOOR: usize = 45;
 pub const MAX_DRUNKARD_STEPS: usize = 400;
 
+// VaultStamper settings
+/// Random spots tried before giving up on stamping a prefab vault
+pub const VAULT_PLACEMENT_ATTEMPTS: usize = 100;
+
 // RandomRoomsBuilder settings
 pub const NUM_ROOMS: usize = 20;
 pub const ROOM_MAX_WIDTH: usize = 10;
 pub const ROOM_MAX_HEIGHT: usize = 10;
+
+// WfcBuilder settings
+/// Collapse attempts (each with a new seed) before falling back to the
+/// cellular automata builder
+pub const WFC_MAX_ATTEMPTS: usize = 10;
+/// Width and height of the tile patterns learned from the sample
+pub const WFC_PATTERN_SIZE: usize = 3;
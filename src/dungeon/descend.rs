@@ -0,0 +1,22 @@
+use super::NextLevelEvent;
+use crate::{
+    common::{Change, PendingChange},
+    prelude::*,
+};
+
+/// Descend to the level requested by [`super::take_stairs`] once the request
+/// is due (see [`PendingChange`]), which replaces the dungeon along with its
+/// monsters and items.
+pub fn descend(
+    mut next_level_events: EventWriter<NextLevelEvent>,
+    mut pending: ResMut<PendingChange>,
+) {
+    let Some(Change::NextLevel(level)) = pending.change() else {
+        return;
+    };
+
+    if pending.tick() {
+        info!("Descending to level {level}");
+        next_level_events.send(NextLevelEvent::new(level));
+    }
+}
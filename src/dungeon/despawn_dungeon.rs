@@ -0,0 +1,13 @@
+use super::{FloorTile, WallTile};
+use crate::prelude::*;
+
+/// Despawn the tiles of the current level before spawning the next one.
+#[allow(clippy::type_complexity)]
+pub fn despawn_dungeon(
+    mut commands: Commands,
+    tiles: Query<Entity, Or<(With<FloorTile>, With<WallTile>)>>,
+) {
+    tiles
+        .iter()
+        .for_each(|tile| commands.entity(tile).despawn_recursive());
+}
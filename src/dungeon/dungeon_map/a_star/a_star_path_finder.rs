@@ -39,6 +39,24 @@ impl AStarPathFinder {
         self.closest_pos
     }
 
+    /// The first step along the path from the start position to the goal, or
+    /// None if no path was found or the start position is the goal.
+    pub fn first_step(&self) -> Option<DungeonPosition> {
+        if !self.path_found() {
+            return None;
+        }
+
+        let mut current = self.goal;
+        while let Some(&prev) = self.came_from.get(&current) {
+            if !self.came_from.contains_key(&prev) {
+                return Some(current);
+            }
+            current = prev;
+        }
+
+        None
+    }
+
     pub fn path_found(&self) -> bool {
         self.closest_distance == 0
     }
@@ -155,8 +173,7 @@ impl AStarPathFinder {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use crate::dungeon::config::{X_MIN, Y_MAX, Y_MIN};
+    use super::{super::MapSize, *};
 
     #[test]
     fn find_no_floors() {
@@ -180,24 +197,46 @@ mod tests {
 
     #[test]
     fn find_single_path() {
-        let fixture = MapFixture::new(X_MIN, 0).tunnel_east();
+        let x_min = MapSize::default().x_min();
+        let fixture = MapFixture::new(x_min, 0).tunnel_east();
 
         let finder = AStarPathFinder::find(fixture.start(), fixture.map.center, &fixture.map);
 
         assert!(finder.path_found());
-        assert_eq!(finder.calculate_path_length(), 1 + -X_MIN as usize);
+        assert_eq!(finder.calculate_path_length(), 1 + -x_min as usize);
+    }
+
+    #[test]
+    fn first_step_along_path() {
+        let x_min = MapSize::default().x_min();
+        let fixture = MapFixture::new(x_min, 0).tunnel_east();
+
+        let finder = AStarPathFinder::find(fixture.start(), fixture.map.center, &fixture.map);
+
+        let expected = DungeonPosition::new(x_min + 1, 0);
+        assert_eq!(finder.first_step(), Some(expected));
+    }
+
+    #[test]
+    fn no_first_step_at_goal() {
+        let fixture = MapFixture::new(0, 0);
+
+        let finder = AStarPathFinder::find(fixture.start(), fixture.map.center, &fixture.map);
+
+        assert_eq!(finder.first_step(), None);
     }
 
     #[test]
     fn find_among_many_paths() {
-        let fixture = MapFixture::new(X_MIN, 20).clear_walls();
+        let x_min = MapSize::default().x_min();
+        let fixture = MapFixture::new(x_min, 20).clear_walls();
 
         let finder = AStarPathFinder::find(fixture.start(), fixture.map.center, &fixture.map);
 
         assert!(finder.path_found());
         assert_eq!(
             finder.calculate_path_length(),
-            (fixture.start().y + 1 + -X_MIN) as usize
+            (fixture.start().y + 1 + -x_min) as usize
         );
     }
 
@@ -221,7 +260,7 @@ mod tests {
 
     impl MapFixture {
         fn new(x: isize, y: isize) -> Self {
-            let mut map = DungeonMap::new();
+            let mut map = DungeonMap::new(MapSize::default());
 
             let start = DungeonPosition::new(x, y);
             map.set_tile_type(&start, TileType::Floor);
@@ -260,7 +299,8 @@ mod tests {
         }
 
         fn with_vertical_barrier(mut self, x: isize) -> Self {
-            for y in Y_MIN..=Y_MAX {
+            let size = self.map.size();
+            for y in size.y_min()..=size.y_max() {
                 let pos = DungeonPosition::new(x, y);
                 self.map.set_tile_type(&pos, TileType::Wall);
             }
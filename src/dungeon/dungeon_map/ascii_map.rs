@@ -0,0 +1,246 @@
+use super::{DungeonMap, DungeonPosition, MapSize, TileType};
+use std::{fmt, str::FromStr};
+
+// Characters of the plain-text map, matching the prefab vaults where they
+// overlap. Player starts are numbered from 1 in starting order.
+const EXIT: char = '>';
+const FLOOR: char = '.';
+const ITEM: char = '!';
+const MONSTER: char = 'M';
+const TREASURE: char = '$';
+const WALL: char = '#';
+
+/// Writes the map as a plain-text grid, one line per row (top row first).
+/// Spawn points are all on floor tiles, so a tile shows its spawn point
+/// instead, favoring players, then monsters, items and treasure.
+impl fmt::Display for DungeonMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let size = self.size();
+        for y in (size.y_min()..=size.y_max()).rev() {
+            let row = (size.x_min()..=size.x_max())
+                .map(|x| self.ascii_char(DungeonPosition::new(x, y)))
+                .collect::<String>();
+            writeln!(f, "{row}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads a map written by [`fmt::Display`], e.g. to test against a fixed
+/// layout. The size comes from the rows, which must all be the same length.
+/// Surrounding whitespace and blank lines are ignored. The center is the exit,
+/// or the origin if there is none.
+impl FromStr for DungeonMap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .map(|row| row.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = rows.first().map_or(0, Vec::len);
+        if width == 0 {
+            return Err("The map has no tiles".to_string());
+        }
+        if let Some(row) = rows.iter().position(|r| r.len() != width) {
+            return Err(format!(
+                "Row {} has {} tiles instead of {width}",
+                row + 1,
+                rows[row].len()
+            ));
+        }
+
+        let size = MapSize::new(width, rows.len());
+        let mut map = DungeonMap::new(size);
+        let mut exit = None;
+        let mut players = vec![];
+        for (row, chars) in rows.iter().enumerate() {
+            for (column, &c) in chars.iter().enumerate() {
+                let pos = DungeonPosition::new(
+                    size.x_min() + column as isize,
+                    size.y_max() - row as isize,
+                );
+                let tile_type = match c {
+                    WALL => TileType::Wall,
+                    FLOOR => TileType::Floor,
+                    EXIT if exit.is_some() => return Err("The map has multiple exits".to_string()),
+                    EXIT => {
+                        exit = Some(pos);
+                        TileType::Exit
+                    }
+                    ITEM => {
+                        map.item_positions.push(pos);
+                        TileType::Floor
+                    }
+                    MONSTER => {
+                        map.monster_starting_positions.push(pos);
+                        TileType::Floor
+                    }
+                    TREASURE => {
+                        map.treasure_positions.push(pos);
+                        TileType::Floor
+                    }
+                    '1'..='9' => {
+                        players.push((c, pos));
+                        TileType::Floor
+                    }
+                    _ => {
+                        return Err(format!(
+                            "Invalid character '{c}' at row {}, column {}",
+                            row + 1,
+                            column + 1
+                        ))
+                    }
+                };
+                map.set_tile_type(&pos, tile_type);
+            }
+        }
+
+        players.sort_by_key(|(c, _)| *c);
+        if players
+            .iter()
+            .zip('1'..)
+            .any(|((c, _), expected)| *c != expected)
+        {
+            return Err("Player starts must be numbered once each from 1".to_string());
+        }
+        map.player_starting_positions = players.into_iter().map(|(_, pos)| pos).collect();
+        map.center = exit.unwrap_or(map.center);
+
+        Ok(map)
+    }
+}
+
+impl DungeonMap {
+    fn ascii_char(&self, pos: DungeonPosition) -> char {
+        match self.get_tile_type(&pos) {
+            TileType::Exit => return EXIT,
+            TileType::Wall => return WALL,
+            TileType::Floor => {}
+        }
+
+        if let Some(player) = self
+            .player_starting_positions
+            .iter()
+            .position(|p| *p == pos)
+        {
+            char::from_digit(player as u32 + 1, 10).expect("At most 9 players fit in a text map")
+        } else if self.monster_starting_positions.contains(&pos) {
+            MONSTER
+        } else if self.item_positions.contains(&pos) {
+            ITEM
+        } else if self.treasure_positions.contains(&pos) {
+            TREASURE
+        } else {
+            FLOOR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::{AStarPathFinder, BspBuilder, BspConfig, DungeonBuilder},
+        *,
+    };
+    use crate::prelude::*;
+    use rstest::rstest;
+
+    const LAYOUT: &str = "
+        #########
+        #1..M..2#
+        #.##.##.#
+        #!..>..$#
+        #########
+    ";
+
+    #[test]
+    fn parse() {
+        let map = LAYOUT.parse::<DungeonMap>().unwrap();
+
+        assert_eq!(map.size(), MapSize::new(9, 5));
+        assert_eq!(map.center, DungeonPosition::new(0, -1));
+        assert_eq!(map.get_tile_type(&map.center), TileType::Exit);
+        assert_eq!(
+            map.player_starting_positions,
+            vec![DungeonPosition::new(-3, 1), DungeonPosition::new(3, 1)]
+        );
+        assert_eq!(
+            map.monster_starting_positions,
+            vec![DungeonPosition::new(0, 1)]
+        );
+        assert_eq!(map.item_positions, vec![DungeonPosition::new(-3, -1)]);
+        assert_eq!(map.treasure_positions, vec![DungeonPosition::new(3, -1)]);
+        assert_eq!(
+            map.get_tile_type(&DungeonPosition::new(-2, 0)),
+            TileType::Wall
+        );
+        assert_eq!(
+            map.get_tile_type(&DungeonPosition::new(0, 0)),
+            TileType::Floor
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let map = LAYOUT.parse::<DungeonMap>().unwrap();
+
+        let text = map.to_string();
+
+        let expected = LAYOUT
+            .lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .map(|row| format!("{row}\n"))
+            .collect::<String>();
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn built_map_round_trip() {
+        let size = MapSize::new(40, 24);
+        let map = BspBuilder::build(&BspConfig::default(), size, 2, &mut RandomGenerator::new());
+
+        let parsed = map.to_string().parse::<DungeonMap>().unwrap();
+
+        assert_eq!(parsed.size(), size);
+        assert_eq!(parsed.center, map.center);
+        assert_eq!(
+            parsed.player_starting_positions,
+            map.player_starting_positions
+        );
+        assert_eq!(parsed.to_string(), map.to_string());
+    }
+
+    #[test]
+    fn path_around_walls() {
+        let map = "
+            #######
+            #1#...#
+            #.#.#.#
+            #...#>#
+            #######
+        "
+        .parse::<DungeonMap>()
+        .unwrap();
+
+        let finder = AStarPathFinder::find(map.player_starting_positions[0], map.center, &map);
+
+        assert!(finder.path_found());
+        assert_eq!(finder.calculate_path_length(), 11);
+    }
+
+    #[rstest]
+    #[case::empty("", "The map has no tiles")]
+    #[case::ragged("###\n##", "Row 2 has 2 tiles instead of 3")]
+    #[case::invalid("#.#\n#x#", "Invalid character 'x' at row 2, column 2")]
+    #[case::two_exits("#>>#", "The map has multiple exits")]
+    #[case::player_gap("#1.3#", "Player starts must be numbered once each from 1")]
+    #[case::same_player("#1.1#", "Player starts must be numbered once each from 1")]
+    fn invalid(#[case] text: &str, #[case] error: &str) {
+        assert_eq!(text.parse::<DungeonMap>().err().as_deref(), Some(error));
+    }
+}
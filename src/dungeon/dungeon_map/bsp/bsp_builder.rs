@@ -0,0 +1,317 @@
+use super::*;
+use crate::{player::PlayerId, prelude::*};
+use std::ops::Range;
+
+/// Smallest width or height of a room
+const MIN_ROOM_SIZE: usize = 3;
+
+/// Builds tidy castle-like levels by recursively splitting the map in two
+/// (binary space partitioning), placing a room in each leaf partition and
+/// joining the rooms of sibling partitions with corridors.
+pub struct BspBuilder {
+    config: BspConfig,
+    map: DungeonMap,
+    rooms: Vec<Room>,
+}
+
+impl DungeonBuilder for BspBuilder {
+    type Config = BspConfig;
+
+    fn build(
+        config: &BspConfig,
+        size: MapSize,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> DungeonMap {
+        info!("Building binary space partition dungeon.");
+        assert!(
+            config.min_leaf_size >= MIN_ROOM_SIZE + 2,
+            "BSP min_leaf_size must be at least {}",
+            MIN_ROOM_SIZE + 2
+        );
+        assert!(
+            size.width >= config.min_leaf_size + 2 && size.height >= config.min_leaf_size + 2,
+            "Map size {size:?} is too small for the BSP min_leaf_size"
+        );
+
+        let mut builder = Self {
+            config: config.clone(),
+            map: DungeonMap::new(size),
+            rooms: vec![],
+        };
+        // the area within the perimeter walls
+        let area = Room::new(
+            size.x_min() + 1,
+            size.y_min() + 1,
+            size.width - 2,
+            size.height - 2,
+        );
+        builder.partition(area, 0, rng);
+
+        builder
+            .set_center()
+            .add_player_starting_positions(num_players, rng)
+            .map
+            .populate(config.num_items, config.num_monsters, rng)
+    }
+}
+
+impl BspBuilder {
+    /// Each player starts at the center of the room nearest a different corner
+    /// of the dungeon, with the first two players in opposite corners.
+    fn add_player_starting_positions(
+        mut self,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> Self {
+        for (player_id, corner) in DungeonCorner::for_players(num_players, rng)
+            .into_iter()
+            .enumerate()
+        {
+            let pos = self.nearest_free_room(corner.pos(self.map.size()));
+            self.ensure_reachable(player_id, pos);
+            self.map.player_starting_positions.push(pos);
+        }
+
+        self
+    }
+
+    /// Carve out a random room within the leaf partition, leaving a wall on
+    /// each side so rooms of neighboring leaves do not merge.
+    fn add_room(&mut self, leaf: Room, rng: &mut RandomGenerator) {
+        let width = rng.gen_range(MIN_ROOM_SIZE..leaf.width - 1);
+        let height = rng.gen_range(MIN_ROOM_SIZE..leaf.height - 1);
+        let x = leaf.x + 1 + rng.gen_range(0..leaf.width - 1 - width) as isize;
+        let y = leaf.y + 1 + rng.gen_range(0..leaf.height - 1 - height) as isize;
+        let room = Room::new(x, y, width, height);
+
+        room.tile_positions()
+            .for_each(|pos| self.map.set_tile_type(&pos, TileType::Floor));
+        self.rooms.push(room);
+    }
+
+    /// Join the two sibling partitions with a corridor between their closest
+    /// pair of rooms.
+    fn connect(&mut self, first: Range<usize>, second: Range<usize>) {
+        let (from, to) = first
+            .flat_map(|a| second.clone().map(move |b| (a, b)))
+            .map(|(a, b)| (self.rooms[a].center(), self.rooms[b].center()))
+            .min_by(|(a1, b1), (a2, b2)| a1.distance(*b1).total_cmp(&a2.distance(*b2)))
+            .expect("Partitions have rooms");
+
+        Tunneler::tunnel(&mut self.map, from, to);
+    }
+
+    /// Tunnel to the dungeon center if the player cannot reach it. Rooms are
+    /// all connected, so this is just a safeguard.
+    fn ensure_reachable(&mut self, player_id: PlayerId, pos: DungeonPosition) {
+        let finder = AStarPathFinder::find(pos, self.map.center, &self.map);
+        if finder.path_found() {
+            return;
+        }
+
+        warn!("No path found from player {player_id} to center.");
+        let player_side = finder.closest_position();
+        let center_side =
+            AStarPathFinder::find(self.map.center, player_side, &self.map).closest_position();
+
+        Tunneler::tunnel(&mut self.map, player_side, center_side);
+    }
+
+    /// The center of the room nearest the given position, skipping the center
+    /// room and rooms already taken by other players (unless there are too few
+    /// rooms).
+    fn nearest_free_room(&self, target: DungeonPosition) -> DungeonPosition {
+        let nearest = |centers: &mut dyn Iterator<Item = DungeonPosition>| {
+            centers.min_by(|a, b| a.distance(target).total_cmp(&b.distance(target)))
+        };
+        let taken = |pos: &DungeonPosition| {
+            *pos == self.map.center || self.map.player_starting_positions.contains(pos)
+        };
+
+        nearest(&mut self.rooms.iter().map(Room::center).filter(|c| !taken(c)))
+            .or_else(|| nearest(&mut self.rooms.iter().map(Room::center)))
+            .expect("Dungeon has rooms")
+    }
+
+    /// Recursively split the area in two until too small or deep enough, adding
+    /// a room to each leaf and connecting sibling partitions. Returns the range
+    /// of the area's rooms, which are contiguous since they are added depth
+    /// first.
+    fn partition(&mut self, area: Room, depth: usize, rng: &mut RandomGenerator) -> Range<usize> {
+        let Some((first, second)) = self.split(area, depth, rng) else {
+            self.add_room(area, rng);
+            return self.rooms.len() - 1..self.rooms.len();
+        };
+
+        let first = self.partition(first, depth + 1, rng);
+        let second = self.partition(second, depth + 1, rng);
+        self.connect(first.clone(), second.clone());
+
+        first.start..second.end
+    }
+
+    /// Reset the map center to the center of the room nearest the absolute
+    /// center.
+    fn set_center(mut self) -> Self {
+        let origin = self.map.center;
+        self.map.center = self
+            .rooms
+            .iter()
+            .map(Room::center)
+            .min_by(|a, b| a.distance(origin).total_cmp(&b.distance(origin)))
+            .expect("Dungeon has rooms");
+
+        self
+    }
+
+    /// Split the area across its longer side (or either side if roughly
+    /// square), unless at the max depth or too small to split.
+    fn split(&self, area: Room, depth: usize, rng: &mut RandomGenerator) -> Option<(Room, Room)> {
+        if depth >= self.config.max_depth {
+            return None;
+        }
+
+        let min = self.config.min_leaf_size;
+        let split_width = match (area.width >= 2 * min, area.height >= 2 * min) {
+            (false, false) => return None,
+            (true, false) => true,
+            (false, true) => false,
+            _ if area.width * 4 > area.height * 5 => true,
+            _ if area.height * 4 > area.width * 5 => false,
+            _ => rng.gen_range(0..2) == 1,
+        };
+
+        if split_width {
+            let width = rng.gen_range(min..area.width - min + 1);
+            Some((
+                Room::new(area.x, area.y, width, area.height),
+                Room::new(
+                    area.x + width as isize,
+                    area.y,
+                    area.width - width,
+                    area.height,
+                ),
+            ))
+        } else {
+            let height = rng.gen_range(min..area.height - min + 1);
+            Some((
+                Room::new(area.x, area.y, area.width, height),
+                Room::new(
+                    area.x,
+                    area.y + height as isize,
+                    area.width,
+                    area.height - height,
+                ),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn rooms_within_perimeter() {
+        let mut rng = RandomGenerator::new();
+
+        let map = BspBuilder::build(&BspConfig::default(), default(), 1, &mut rng);
+
+        assert!(map
+            .tiles()
+            .filter(|t| map.size().at_perimeter(&t.pos))
+            .all(|t| t.tile_type == TileType::Wall));
+        let floor_count = map
+            .tiles()
+            .filter(|t| t.tile_type == TileType::Floor)
+            .count();
+        assert!(floor_count > 100, "floor_count={floor_count}");
+    }
+
+    #[rstest]
+    #[case::small(MapSize::new(40, 24))]
+    #[case::odd(MapSize::new(31, 17))]
+    fn custom_size(#[case] size: MapSize) {
+        let mut rng = RandomGenerator::new();
+
+        let map = BspBuilder::build(&BspConfig::default(), size, 2, &mut rng);
+
+        assert_eq!(map.size(), size);
+        assert_eq!(map.tiles().count(), size.num_tiles());
+        assert!(map
+            .tiles()
+            .filter(|t| size.at_perimeter(&t.pos))
+            .all(|t| t.tile_type == TileType::Wall));
+        for player_pos in &map.player_starting_positions {
+            assert!(AStarPathFinder::find(*player_pos, map.center, &map).path_found());
+        }
+    }
+
+    #[test]
+    fn single_room() {
+        let config = BspConfig {
+            max_depth: 0,
+            ..default()
+        };
+        let mut rng = RandomGenerator::new();
+
+        let map = BspBuilder::build(&config, default(), 2, &mut rng);
+
+        assert_eq!(map.get_tile_type(&map.center), TileType::Exit);
+        assert_eq!(map.player_starting_positions.len(), 2);
+    }
+
+    #[test]
+    fn exit_at_dungeon_center() {
+        let mut rng = RandomGenerator::new();
+
+        let map = BspBuilder::build(&BspConfig::default(), default(), 1, &mut rng);
+
+        assert_eq!(map.get_tile_type(&map.center), TileType::Exit);
+        assert!(!map.item_positions.contains(&map.center));
+    }
+
+    #[rstest]
+    #[case::one_player(1)]
+    #[case::four_players(4)]
+    fn player_positions(#[case] num_players: usize) {
+        let mut rng = RandomGenerator::new();
+
+        let map = BspBuilder::build(&BspConfig::default(), default(), num_players, &mut rng);
+
+        assert_eq!(map.player_starting_positions.len(), num_players);
+        for player_pos in &map.player_starting_positions {
+            assert_ne!(*player_pos, map.center);
+            assert!(AStarPathFinder::find(*player_pos, map.center, &map).path_found());
+        }
+    }
+
+    #[test]
+    fn all_floors_reachable() {
+        let mut rng = RandomGenerator::new();
+
+        let map = BspBuilder::build(&BspConfig::default(), default(), 1, &mut rng);
+
+        let mut distances = DijkstraMap::new();
+        distances.compute(map.center, &map);
+        let unreachable = map
+            .tiles()
+            .filter(|t| t.tile_type == TileType::Floor)
+            .find(|t| distances.distance(&t.pos).is_none())
+            .map(|t| t.pos);
+        assert!(unreachable.is_none(), "unreachable floor {unreachable:?}");
+    }
+
+    #[test]
+    fn dungeon_has_items_and_monsters() {
+        let config = BspConfig::default();
+        let mut rng = RandomGenerator::new();
+
+        let map = BspBuilder::build(&config, default(), 1, &mut rng);
+
+        assert_eq!(map.item_positions.len(), config.num_items);
+        assert_eq!(map.monster_starting_positions.len(), config.num_monsters);
+    }
+}
@@ -0,0 +1,24 @@
+use super::{BSP_MAX_DEPTH, BSP_MIN_LEAF_SIZE, NUM_ITEMS, NUM_MONSTERS};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct BspConfig {
+    /// Times the map is recursively split in two
+    pub max_depth: usize,
+    /// Smallest width or height of a partition
+    pub min_leaf_size: usize,
+    pub num_items: usize,
+    pub num_monsters: usize,
+}
+
+impl Default for BspConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: BSP_MAX_DEPTH,
+            min_leaf_size: BSP_MIN_LEAF_SIZE,
+            num_items: NUM_ITEMS,
+            num_monsters: NUM_MONSTERS,
+        }
+    }
+}
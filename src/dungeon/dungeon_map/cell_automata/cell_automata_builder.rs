@@ -1,56 +1,46 @@
 use super::*;
 use crate::{player::PlayerId, prelude::*};
-use rand::prelude::*;
 
 pub struct CellAutomataBuilder {
+    config: CellAutomataConfig,
     map: DungeonMap,
 }
 
-impl CellAutomataBuilder {
-    pub fn build(rng: &mut RandomGenerator) -> DungeonMap {
+impl DungeonBuilder for CellAutomataBuilder {
+    type Config = CellAutomataConfig;
+
+    fn build(
+        config: &CellAutomataConfig,
+        size: MapSize,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> DungeonMap {
         info!("Building cellular automata dungeon.");
 
         Self {
-            map: DungeonMap::new(),
+            config: config.clone(),
+            map: DungeonMap::new(size),
         }
         .randomize_tiles(rng)
         .grow_cells()
         .set_center()
-        .add_player_starting_positions(rng)
-        .add_items(rng)
-        .add_monster_starting_positions(rng)
+        .add_player_starting_positions(num_players, rng)
         .map
+        .populate(config.num_items, config.num_monsters, rng)
     }
+}
 
-    fn add_items(mut self, rng: &mut RandomGenerator) -> Self {
-        self.map.item_positions = self
-            .map
-            .spawnable_positions()
-            .choose_multiple(rng, NUM_ITEMS);
-
-        self
-    }
-
-    fn add_monster_starting_positions(mut self, rng: &mut RandomGenerator) -> Self {
-        self.map.monster_starting_positions = self
-            .map
-            .spawnable_positions()
-            .choose_multiple(rng, NUM_MONSTERS);
-
-        self
-    }
-
-    /// Randomly assign player starting positions to opposite corners of the
-    /// dungeon.
-    fn add_player_starting_positions(mut self, rng: &mut RandomGenerator) -> Self {
-        let quadrant = DungeonCorner::random(rng);
-
-        let player0_pos = self.determine_player_pos(quadrant);
-        self.map.player_starting_positions.push(player0_pos);
-
-        if config::GAME_MODE != GameMode::SinglePlayer {
-            let player1_pos = self.determine_player_pos(quadrant.opposite());
-            self.map.player_starting_positions.push(player1_pos);
+impl CellAutomataBuilder {
+    /// Randomly assign player starting positions to different corners of the
+    /// dungeon, with the first two players in opposite corners.
+    fn add_player_starting_positions(
+        mut self,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> Self {
+        for quadrant in DungeonCorner::for_players(num_players, rng) {
+            let player_pos = self.determine_player_pos(quadrant);
+            self.map.player_starting_positions.push(player_pos);
         }
 
         self
@@ -60,7 +50,9 @@ impl CellAutomataBuilder {
     /// Ensure player can reach dungeon center, creating a tunnel if necessary.
     fn determine_player_pos(&mut self, quadrant: DungeonCorner) -> DungeonPosition {
         let radius = 1;
-        let pos = self.map.find_nearest_floor_tile(quadrant.pos(), radius);
+        let pos = self
+            .map
+            .find_nearest_floor_tile(quadrant.pos(self.map.size()), radius);
 
         let player_id = self.map.player_starting_positions.len();
 
@@ -75,18 +67,20 @@ impl CellAutomataBuilder {
     /// Smooth out the randomly assigned tiles by converting tiles to floor unless
     /// surrounded by too many walls (or no walls at all).
     fn grow_cells(mut self) -> Self {
-        self.map = CellGrower::grow(self.map);
+        self.map = CellGrower::grow(self.map, self.config.generations);
 
         self
     }
 
-    /// Randomly assign tiles within the map, slightly favoring floors.
+    /// Randomly assign tiles within the map per the configured floor chance.
     /// Map perimeter is left as walls.
     fn randomize_tiles(mut self, rng: &mut RandomGenerator) -> Self {
+        let wall_chance = 100 - self.config.floor_chance;
+        let size = self.map.size();
         self.map
             .tiles()
-            .filter(|t| !t.pos.at_perimeter())
-            .filter(|t| rng.gen_range(0..100) >= 55 && t.tile_type == TileType::Wall)
+            .filter(|t| !size.at_perimeter(&t.pos))
+            .filter(|t| rng.gen_range(0..100) >= wall_chance && t.tile_type == TileType::Wall)
             .map(|t| t.pos)
             .collect::<Vec<_>>()
             .iter()
@@ -118,12 +112,14 @@ impl CellAutomataBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
 
     #[test]
     fn create_dungeon_with_floor_and_walls() {
         let mut rng = RandomGenerator::new();
 
-        let map = CellAutomataBuilder::build(&mut rng);
+        let map =
+            CellAutomataBuilder::build(&CellAutomataConfig::default(), default(), 1, &mut rng);
 
         assert_eq!(map.tiles().count(), MAP_WIDTH * MAP_HEIGHT);
         let floor_count = map
@@ -139,40 +135,51 @@ mod tests {
     }
 
     #[test]
-    fn dungeon_center_most_floor_tile() {
+    fn exit_at_dungeon_center() {
         let mut rng = RandomGenerator::new();
 
-        let map = CellAutomataBuilder::build(&mut rng);
+        let map =
+            CellAutomataBuilder::build(&CellAutomataConfig::default(), default(), 1, &mut rng);
 
-        assert_eq!(map.get_tile_type(&map.center), TileType::Floor);
+        assert_eq!(map.get_tile_type(&map.center), TileType::Exit);
     }
 
-    #[test]
-    fn player_position() {
+    #[rstest]
+    #[case::one_player(1)]
+    #[case::four_players(4)]
+    fn player_positions(#[case] num_players: usize) {
         let mut rng = RandomGenerator::new();
 
-        let map = CellAutomataBuilder::build(&mut rng);
+        let map = CellAutomataBuilder::build(
+            &CellAutomataConfig::default(),
+            default(),
+            num_players,
+            &mut rng,
+        );
 
-        assert_eq!(map.player_starting_positions.len(), 1);
-        let player_pos = map.player_starting_positions[0];
-        assert!(AStarPathFinder::find(player_pos, map.center, &map).path_found());
+        assert_eq!(map.player_starting_positions.len(), num_players);
+        for player_pos in &map.player_starting_positions {
+            assert!(AStarPathFinder::find(*player_pos, map.center, &map).path_found());
+        }
     }
 
     #[test]
     fn dungeon_has_items() {
+        let config = CellAutomataConfig::default();
         let mut rng = RandomGenerator::new();
 
-        let map = CellAutomataBuilder::build(&mut rng);
+        let map = CellAutomataBuilder::build(&config, default(), 1, &mut rng);
 
-        assert_eq!(map.item_positions.len(), NUM_ITEMS);
+        assert_eq!(map.item_positions.len(), config.num_items);
     }
 
     #[test]
     fn dungeon_has_monsters() {
+        let config = CellAutomataConfig::default();
         let mut rng = RandomGenerator::new();
 
-        let map = CellAutomataBuilder::build(&mut rng);
+        let map = CellAutomataBuilder::build(&config, default(), 1, &mut rng);
 
-        assert_eq!(map.monster_starting_positions.len(), NUM_MONSTERS);
+        assert_eq!(map.monster_starting_positions.len(), config.num_monsters);
     }
 }
@@ -6,11 +6,12 @@ pub(super) struct CellGrower {
 }
 
 impl CellGrower {
-    /// Grow each non-perimeter tile based on the number of adjacent wall tiles.
-    pub fn grow(map: DungeonMap) -> DungeonMap {
+    /// Grow each non-perimeter tile based on the number of adjacent wall tiles,
+    /// over the given number of generations.
+    pub fn grow(map: DungeonMap, generations: usize) -> DungeonMap {
         let mut grower = Self { map };
 
-        for _ in 0..10 {
+        for _ in 0..generations {
             grower.generation().iter().for_each(|tile| {
                 grower.map.set_tile_type(&tile.pos, tile.tile_type);
             });
@@ -31,7 +32,7 @@ impl CellGrower {
     /// Otherwise convert to floor.
     /// Perimeter tiles are not converted.
     fn maybe_convert_tile(&self, tile: DungeonTile) -> Option<DungeonTile> {
-        if tile.pos.at_perimeter() {
+        if self.map.size().at_perimeter(&tile.pos) {
             return None;
         }
 
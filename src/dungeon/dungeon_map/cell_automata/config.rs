@@ -0,0 +1,24 @@
+use super::{CELL_GENERATIONS, FLOOR_CHANCE, NUM_ITEMS, NUM_MONSTERS};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct CellAutomataConfig {
+    /// Chance (in percent) of each tile starting out as floor
+    pub floor_chance: usize,
+    /// Rounds of smoothing the random tiles
+    pub generations: usize,
+    pub num_items: usize,
+    pub num_monsters: usize,
+}
+
+impl Default for CellAutomataConfig {
+    fn default() -> Self {
+        Self {
+            floor_chance: FLOOR_CHANCE,
+            generations: CELL_GENERATIONS,
+            num_items: NUM_ITEMS,
+            num_monsters: NUM_MONSTERS,
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use super::{DungeonMap, DungeonPosition, MapPos, MapSize};
+use bevy::prelude::Component;
+use std::collections::VecDeque;
+
+pub type DijkstraDistance = u16;
+
+/// Distance (in steps) from every tile in the dungeon to a goal (e.g. a player),
+/// computed via a breadth-first flood fill that does not cross walls. Stepping
+/// to the neighbor with the lowest distance leads to the goal around any walls.
+/// See [Dijkstra Maps](https://www.roguebasin.com/index.php/The_Incredible_Power_of_Dijkstra_Maps).
+/// The fill only depends on the goal and the map, so it is identical on all p2p
+/// clients.
+#[derive(Clone, Component)]
+pub struct DijkstraMap {
+    /// Indexed like the [`DungeonMap`] tiles. None if the goal is unreachable.
+    distances: Vec<Option<DijkstraDistance>>,
+    /// Size of the map the distances were computed for
+    size: MapSize,
+}
+
+impl DijkstraMap {
+    /// An empty map, with no distances until computed.
+    pub fn new() -> Self {
+        Self {
+            distances: vec![],
+            size: MapSize::new(0, 0),
+        }
+    }
+
+    /// Recompute all distances to the given goal, resizing to the map if needed.
+    pub fn compute(&mut self, goal: DungeonPosition, map: &DungeonMap) {
+        self.size = map.size();
+        self.distances.clear();
+        self.distances.resize(self.size.num_tiles(), None);
+        self.distances[MapPos::new(&goal, self.size).to_idx()] = Some(0);
+
+        let mut frontier = VecDeque::from([goal]);
+        while let Some(pos) = frontier.pop_front() {
+            let next_distance = self.distance(&pos).expect("Inconceivable!") + 1;
+
+            for neighbor in Self::neighbors(pos, map) {
+                let idx = MapPos::new(&neighbor, self.size).to_idx();
+                if self.distances[idx].is_none() {
+                    self.distances[idx] = Some(next_distance);
+                    frontier.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    /// The number of steps from the given position to the goal, if reachable.
+    pub fn distance(&self, pos: &DungeonPosition) -> Option<DijkstraDistance> {
+        let map_pos = MapPos::new(pos, self.size);
+
+        map_pos
+            .is_valid()
+            .then(|| self.distances[map_pos.to_idx()])
+            .flatten()
+    }
+
+    /// Returns the neighbors of the given position that are valid and not walls,
+    /// always in the same order.
+    fn neighbors(
+        pos: DungeonPosition,
+        map: &DungeonMap,
+    ) -> impl Iterator<Item = DungeonPosition> + use<'_> {
+        [
+            DungeonPosition::new(pos.x, pos.y + 1),
+            DungeonPosition::new(pos.x, pos.y - 1),
+            DungeonPosition::new(pos.x - 1, pos.y),
+            DungeonPosition::new(pos.x + 1, pos.y),
+        ]
+        .into_iter()
+        .filter(|n| map.is_walkable(n))
+    }
+}
+
+impl Default for DijkstraMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dungeon::dungeon_map::TileType;
+
+    #[test]
+    fn distance_at_goal() {
+        let map = create_open_map();
+        let goal = DungeonPosition::new(0, 0);
+
+        let dijkstra_map = create_dijkstra_map(goal, &map);
+
+        assert_eq!(dijkstra_map.distance(&goal), Some(0));
+    }
+
+    #[test]
+    fn distance_in_open_map() {
+        let map = create_open_map();
+        let goal = DungeonPosition::new(0, 0);
+
+        let dijkstra_map = create_dijkstra_map(goal, &map);
+
+        let pos = DungeonPosition::new(-3, 4);
+        assert_eq!(dijkstra_map.distance(&pos), Some(7));
+    }
+
+    #[test]
+    fn distance_around_wall() {
+        let mut map = create_open_map();
+        // vertical wall at x=2 from y=-5 to y=5
+        for y in -5..=5 {
+            map.set_tile_type(&DungeonPosition::new(2, y), TileType::Wall);
+        }
+        let goal = DungeonPosition::new(0, 0);
+
+        let dijkstra_map = create_dijkstra_map(goal, &map);
+
+        // up 6 to get past the wall, 4 east and then 6 down
+        let pos = DungeonPosition::new(4, 0);
+        assert_eq!(dijkstra_map.distance(&pos), Some(16));
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut map = DungeonMap::new(MapSize::default());
+        let goal = DungeonPosition::new(0, 0);
+        map.set_tile_type(&goal, TileType::Floor);
+        let isolated = DungeonPosition::new(5, 5);
+        map.set_tile_type(&isolated, TileType::Floor);
+
+        let dijkstra_map = create_dijkstra_map(goal, &map);
+
+        assert_eq!(dijkstra_map.distance(&isolated), None);
+    }
+
+    #[test]
+    fn recompute_for_new_goal() {
+        let map = create_open_map();
+        let mut dijkstra_map = create_dijkstra_map(DungeonPosition::new(0, 0), &map);
+
+        let goal = DungeonPosition::new(10, 0);
+        dijkstra_map.compute(goal, &map);
+
+        assert_eq!(dijkstra_map.distance(&goal), Some(0));
+        assert_eq!(dijkstra_map.distance(&DungeonPosition::new(0, 0)), Some(10));
+    }
+
+    fn create_dijkstra_map(goal: DungeonPosition, map: &DungeonMap) -> DijkstraMap {
+        let mut dijkstra_map = DijkstraMap::new();
+        dijkstra_map.compute(goal, map);
+
+        dijkstra_map
+    }
+
+    fn create_open_map() -> DungeonMap {
+        let mut map = DungeonMap::new(MapSize::default());
+        map.tiles()
+            .map(|t| t.pos)
+            .collect::<Vec<_>>()
+            .iter()
+            .for_each(|pos| map.set_tile_type(pos, TileType::Floor));
+
+        map
+    }
+}
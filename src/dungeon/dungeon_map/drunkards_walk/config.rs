@@ -1,25 +1,22 @@
-use super::{NUM_ITEMS, NUM_MONSTERS, PERCENT_FLOOR};
-use crate::config;
+use super::{MAX_DRUNKARD_STEPS, NUM_ITEMS, NUM_MONSTERS, PERCENT_FLOOR};
+use serde::Deserialize;
 
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
 pub struct DrunkardsWalkConfig {
+    /// Steps a drunkard staggers before passing out
+    pub max_steps: usize,
     pub num_items: usize,
     pub num_monsters: usize,
-    pub num_players: usize,
     pub percent_floor: usize,
 }
 
 impl Default for DrunkardsWalkConfig {
     fn default() -> Self {
-        let num_players = if config::GAME_MODE == config::GameMode::SinglePlayer {
-            1
-        } else {
-            2
-        };
-
         Self {
+            max_steps: MAX_DRUNKARD_STEPS,
             num_items: NUM_ITEMS,
             num_monsters: NUM_MONSTERS,
-            num_players,
             percent_floor: PERCENT_FLOOR,
         }
     }
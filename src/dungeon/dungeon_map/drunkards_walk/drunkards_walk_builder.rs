@@ -1,6 +1,5 @@
 use super::*;
 use crate::prelude::*;
-use rand::prelude::*;
 
 pub struct DrunkardsWalkBuilder {
     config: DrunkardsWalkConfig,
@@ -8,58 +7,46 @@ pub struct DrunkardsWalkBuilder {
     min_floor_count: usize,
 }
 
-impl DrunkardsWalkBuilder {
-    pub fn build(config: DrunkardsWalkConfig, rng: &mut RandomGenerator) -> DungeonMap {
+impl DungeonBuilder for DrunkardsWalkBuilder {
+    type Config = DrunkardsWalkConfig;
+
+    fn build(
+        config: &DrunkardsWalkConfig,
+        size: MapSize,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> DungeonMap {
         info!("Building drunkards walk dungeon.");
 
-        let min_floor_count = (MAP_WIDTH * MAP_HEIGHT) * config.percent_floor / 100;
+        let min_floor_count = size.num_tiles() * config.percent_floor / 100;
 
         Self {
-            config,
-            map: DungeonMap::new(),
+            config: config.clone(),
+            map: DungeonMap::new(size),
             min_floor_count,
         }
-        .add_player_positions(rng)
+        .add_player_positions(num_players, rng)
         .tunnel(rng)
         .connect_players()
-        .add_items(rng)
-        .add_monsters(rng)
         .map
+        .populate(config.num_items, config.num_monsters, rng)
     }
+}
 
-    fn add_items(mut self, rng: &mut RandomGenerator) -> Self {
-        self.map.item_positions = self
-            .map
-            .spawnable_positions()
-            .choose_multiple(rng, self.config.num_items);
-
-        self
-    }
-
-    fn add_monsters(mut self, rng: &mut RandomGenerator) -> Self {
-        self.map.monster_starting_positions = self
-            .map
-            .spawnable_positions()
-            .choose_multiple(rng, self.config.num_monsters);
-
-        self
-    }
-
-    /// Add players to opposite corners of the dungeon.
-    fn add_player_positions(mut self, rng: &mut RandomGenerator) -> Self {
-        let corner = DungeonCorner::random(rng);
-        self.map.player_starting_positions.push(corner.pos());
-
-        if self.config.num_players == 2 {
-            self.map
-                .player_starting_positions
-                .push(corner.opposite().pos());
-        }
+impl DrunkardsWalkBuilder {
+    /// Add players to different corners of the dungeon, with the first two in
+    /// opposite corners.
+    fn add_player_positions(mut self, num_players: usize, rng: &mut RandomGenerator) -> Self {
+        let size = self.map.size();
+        self.map.player_starting_positions = DungeonCorner::for_players(num_players, rng)
+            .iter()
+            .map(|corner| corner.pos(size))
+            .collect();
 
         self
     }
 
-    /// Ensure both players can reach the center of the dungeon, tunneling if
+    /// Ensure all players can reach the center of the dungeon, tunneling if
     /// necessary.
     fn connect_players(mut self) -> Self {
         let center = self.map.center;
@@ -85,7 +72,7 @@ impl DrunkardsWalkBuilder {
         let mut stagger_steps = 0;
         let mut retries = 0;
 
-        while stagger_steps <= MAX_DRUNKARD_STEPS && retries < 10 {
+        while stagger_steps <= self.config.max_steps && retries < 10 {
             self.map.set_tile_type(&drunkard_pos, TileType::Floor);
 
             if let Some(pos) = self.step(drunkard_pos, rng) {
@@ -122,8 +109,10 @@ impl DrunkardsWalkBuilder {
             _ => unreachable!(),
         };
 
-        let in_bounds =
-            |p: DungeonPosition| p.x > X_MIN && p.x < X_MAX && p.y > Y_MIN && p.y < Y_MAX;
+        let size = self.map.size();
+        let in_bounds = |p: DungeonPosition| {
+            p.x > size.x_min() && p.x < size.x_max() && p.y > size.y_min() && p.y < size.y_max()
+        };
 
         in_bounds(random_step).then_some(random_step)
     }
@@ -138,10 +127,11 @@ impl DrunkardsWalkBuilder {
             self.drunkard(&pos, rng);
         }
 
+        let size = self.map.size();
         while self.insufficient_floor() {
             let random_pos = DungeonPosition::new(
-                rng.gen_range(X_MIN + 1..X_MAX - 1),
-                rng.gen_range(Y_MIN + 1..Y_MAX - 1),
+                rng.gen_range(size.x_min() + 1..size.x_max() - 1),
+                rng.gen_range(size.y_min() + 1..size.y_max() - 1),
             );
 
             self.drunkard(&random_pos, rng);
@@ -162,7 +152,7 @@ mod tests {
         let mut rng = RandomGenerator::new();
         let percent_floor = config.percent_floor;
 
-        let map = DrunkardsWalkBuilder::build(config, &mut rng);
+        let map = DrunkardsWalkBuilder::build(&config, default(), 1, &mut rng);
 
         let tile_count = map.tiles().count();
         assert_eq!(tile_count, MAP_WIDTH * MAP_HEIGHT);
@@ -180,14 +170,12 @@ mod tests {
     #[rstest]
     #[case::one_player(1)]
     #[case::two_players(2)]
+    #[case::four_players(4)]
     fn add_player_position(#[case] num_players: usize) {
-        let config = DrunkardsWalkConfig {
-            num_players,
-            ..default()
-        };
+        let config = DrunkardsWalkConfig::default();
         let mut rng = RandomGenerator::new();
 
-        let map = DrunkardsWalkBuilder::build(config, &mut rng);
+        let map = DrunkardsWalkBuilder::build(&config, default(), num_players, &mut rng);
 
         assert_eq!(
             map.player_starting_positions.len(),
@@ -198,7 +186,7 @@ mod tests {
             .iter()
             .enumerate()
             .for_each(|(player_id, pos)| {
-                let edge_distance = edge_distance(pos);
+                let edge_distance = edge_distance(pos, map.size());
                 assert!(
                     edge_distance < 4,
                     "player {player_id} too far from edge at {edge_distance}"
@@ -210,13 +198,8 @@ mod tests {
     fn players_on_opposite_corners() {
         let mut rng = RandomGenerator::new();
 
-        let map = DrunkardsWalkBuilder::build(
-            DrunkardsWalkConfig {
-                num_players: 2,
-                ..default()
-            },
-            &mut rng,
-        );
+        let map =
+            DrunkardsWalkBuilder::build(&DrunkardsWalkConfig::default(), default(), 2, &mut rng);
 
         let pos1 = map.player_starting_positions[0];
         let pos2 = map.player_starting_positions[1];
@@ -232,7 +215,12 @@ mod tests {
         let mut rng = RandomGenerator::new();
 
         for attempt in 1..=10 {
-            let map = DrunkardsWalkBuilder::build(DrunkardsWalkConfig::default(), &mut rng);
+            let map = DrunkardsWalkBuilder::build(
+                &DrunkardsWalkConfig::default(),
+                default(),
+                1,
+                &mut rng,
+            );
 
             let player_pos = map.player_starting_positions[0];
             let finder = AStarPathFinder::find(player_pos, map.center, &map);
@@ -244,13 +232,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn exit_at_center() {
+        let mut rng = RandomGenerator::new();
+
+        let map =
+            DrunkardsWalkBuilder::build(&DrunkardsWalkConfig::default(), default(), 1, &mut rng);
+
+        assert_eq!(map.get_tile_type(&map.center), TileType::Exit);
+        assert!(!map.item_positions.contains(&map.center));
+    }
+
     #[test]
     fn add_items() {
         let config = DrunkardsWalkConfig::default();
         let num_items = config.num_items;
         let mut rng = RandomGenerator::new();
 
-        let map = DrunkardsWalkBuilder::build(DrunkardsWalkConfig::default(), &mut rng);
+        let map = DrunkardsWalkBuilder::build(&config, default(), 1, &mut rng);
 
         assert_eq!(map.item_positions.len(), num_items);
     }
@@ -261,17 +260,35 @@ mod tests {
         let num_monsters = config.num_monsters;
         let mut rng = RandomGenerator::new();
 
-        let map = DrunkardsWalkBuilder::build(DrunkardsWalkConfig::default(), &mut rng);
+        let map = DrunkardsWalkBuilder::build(&config, default(), 1, &mut rng);
 
         assert_eq!(map.monster_starting_positions.len(), num_monsters);
     }
 
-    fn edge_distance(pos: &DungeonPosition) -> usize {
+    #[test]
+    fn small_map() {
+        let config = DrunkardsWalkConfig::default();
+        let size = MapSize::new(40, 24);
+        let mut rng = RandomGenerator::new();
+
+        let map = DrunkardsWalkBuilder::build(&config, size, 2, &mut rng);
+
+        assert_eq!(map.tiles().count(), size.num_tiles());
+        assert!(map
+            .tiles()
+            .all(|t| t.pos.x >= size.x_min() && t.pos.x <= size.x_max()));
+        for pos in &map.player_starting_positions {
+            assert!(edge_distance(pos, size) < 4);
+            assert!(AStarPathFinder::find(*pos, map.center, &map).path_found());
+        }
+    }
+
+    fn edge_distance(pos: &DungeonPosition, size: MapSize) -> usize {
         [
-            pos.distance(DungeonPosition::new(X_MIN, Y_MAX)),
-            pos.distance(DungeonPosition::new(X_MAX, Y_MAX)),
-            pos.distance(DungeonPosition::new(X_MAX, Y_MIN)),
-            pos.distance(DungeonPosition::new(X_MIN, Y_MIN)),
+            pos.distance(DungeonPosition::new(size.x_min(), size.y_max())),
+            pos.distance(DungeonPosition::new(size.x_max(), size.y_max())),
+            pos.distance(DungeonPosition::new(size.x_max(), size.y_min())),
+            pos.distance(DungeonPosition::new(size.x_min(), size.y_min())),
         ]
         .iter()
         .map(|d| d.round().abs() as usize)
@@ -0,0 +1,160 @@
+use super::{
+    BspBuilder, BspConfig, CellAutomataBuilder, CellAutomataConfig, DrunkardsWalkBuilder,
+    DrunkardsWalkConfig, DungeonMap, MapSize, RandomRoomsBuilder, RandomRoomsConfig, WfcBuilder,
+    WfcConfig,
+};
+use crate::prelude::*;
+use serde::Deserialize;
+
+/// An algorithm that builds a new [`DungeonMap`] of the given size from its
+/// config.
+pub trait DungeonBuilder {
+    type Config;
+
+    fn build(
+        config: &Self::Config,
+        size: MapSize,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> DungeonMap;
+}
+
+/// The dungeon builders to pick from when spawning a level, as loaded from
+/// `assets/builders.ron`.
+#[derive(Asset, Deserialize, TypePath)]
+pub struct BuilderData {
+    pub builders: Vec<WeightedBuilder>,
+}
+
+impl BuilderData {
+    /// Pick a builder at random, in proportion to its weight.
+    pub fn pick(&self, rng: &mut RandomGenerator) -> &WeightedBuilder {
+        let total = self.builders.iter().map(|b| b.weight).sum::<u32>();
+        assert!(total > 0, "No dungeon builder has a weight");
+
+        let mut roll = rng.gen_range(0..total);
+        self.builders
+            .iter()
+            .find(|b| {
+                if roll < b.weight {
+                    return true;
+                }
+                roll -= b.weight;
+                false
+            })
+            .expect("Roll exceeds total weight")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WeightedBuilder {
+    pub builder: BuilderConfig,
+    /// Size of the maps built, which defaults to `MAP_WIDTH` by `MAP_HEIGHT`
+    #[serde(default)]
+    pub size: MapSize,
+    /// Relative chance of the builder being picked. Zero disables it.
+    pub weight: u32,
+}
+
+impl WeightedBuilder {
+    pub fn build(&self, num_players: usize, rng: &mut RandomGenerator) -> DungeonMap {
+        self.builder.build(self.size, num_players, rng)
+    }
+}
+
+/// A dungeon builder along with its settings.
+#[derive(Clone, Debug, Deserialize)]
+pub enum BuilderConfig {
+    Bsp(BspConfig),
+    CellAutomata(CellAutomataConfig),
+    DrunkardsWalk(DrunkardsWalkConfig),
+    RandomRooms(RandomRoomsConfig),
+    Wfc(WfcConfig),
+}
+
+impl BuilderConfig {
+    pub fn build(
+        &self,
+        size: MapSize,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> DungeonMap {
+        match self {
+            Self::Bsp(config) => BspBuilder::build(config, size, num_players, rng),
+            Self::CellAutomata(config) => {
+                CellAutomataBuilder::build(config, size, num_players, rng)
+            }
+            Self::DrunkardsWalk(config) => {
+                DrunkardsWalkBuilder::build(config, size, num_players, rng)
+            }
+            Self::RandomRooms(config) => RandomRoomsBuilder::build(config, size, num_players, rng),
+            Self::Wfc(config) => WfcBuilder::build(config, size, num_players, rng),
+        }
+    }
+
+    /// The name of the builder as written in `builders.ron`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bsp(_) => "Bsp",
+            Self::CellAutomata(_) => "CellAutomata",
+            Self::DrunkardsWalk(_) => "DrunkardsWalk",
+            Self::RandomRooms(_) => "RandomRooms",
+            Self::Wfc(_) => "Wfc",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pick_skips_zero_weight() {
+        let data = BuilderData {
+            builders: vec![
+                WeightedBuilder {
+                    builder: BuilderConfig::CellAutomata(default()),
+                    size: default(),
+                    weight: 0,
+                },
+                WeightedBuilder {
+                    builder: BuilderConfig::RandomRooms(default()),
+                    size: default(),
+                    weight: 3,
+                },
+            ],
+        };
+        let mut rng = RandomGenerator::new();
+
+        for _ in 0..20 {
+            assert!(matches!(
+                data.pick(&mut rng).builder,
+                BuilderConfig::RandomRooms(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn build_with_size() {
+        let size = MapSize::new(40, 24);
+        let builder = WeightedBuilder {
+            builder: BuilderConfig::CellAutomata(default()),
+            size,
+            weight: 1,
+        };
+
+        let map = builder.build(2, &mut RandomGenerator::new());
+
+        assert_eq!(map.size(), size);
+        assert_eq!(map.tiles().count(), size.num_tiles());
+        assert_eq!(map.player_starting_positions.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "No dungeon builder has a weight")]
+    fn pick_requires_weight() {
+        let data = BuilderData { builders: vec![] };
+
+        data.pick(&mut RandomGenerator::new());
+    }
+}
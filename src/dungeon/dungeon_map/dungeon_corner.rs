@@ -1,4 +1,4 @@
-use super::{DungeonPosition, X_MAX, X_MIN, Y_MAX, Y_MIN};
+use super::{DungeonPosition, MapSize};
 use crate::common::RandomGenerator;
 
 /// Each corner of the dungeon within the perimeter (i.e. excluding the border
@@ -12,6 +12,28 @@ pub(super) enum DungeonCorner {
 }
 
 impl DungeonCorner {
+    /// The starting corner of each player: a random one for the first player,
+    /// the opposite one for the second and the remaining two for any others.
+    pub fn for_players(num_players: usize, rng: &mut RandomGenerator) -> Vec<Self> {
+        let first = Self::random(rng);
+        let third = first.adjacent();
+
+        [first, first.opposite(), third, third.opposite()]
+            .into_iter()
+            .take(num_players)
+            .collect()
+    }
+
+    /// The corner on the same side (top or bottom) of the dungeon
+    pub fn adjacent(&self) -> Self {
+        match self {
+            DungeonCorner::BottomLeft => DungeonCorner::BottomRight,
+            DungeonCorner::BottomRight => DungeonCorner::BottomLeft,
+            DungeonCorner::TopLeft => DungeonCorner::TopRight,
+            DungeonCorner::TopRight => DungeonCorner::TopLeft,
+        }
+    }
+
     pub fn random(rng: &mut RandomGenerator) -> Self {
         match rng.gen_range(0..4) {
             0 => DungeonCorner::BottomLeft,
@@ -31,12 +53,16 @@ impl DungeonCorner {
         }
     }
 
-    pub fn pos(&self) -> DungeonPosition {
+    /// The corner position within the perimeter of a map of the given size.
+    pub fn pos(&self, size: MapSize) -> DungeonPosition {
+        let (x_min, x_max) = (size.x_min() + 1, size.x_max() - 1);
+        let (y_min, y_max) = (size.y_min() + 1, size.y_max() - 1);
+
         match self {
-            DungeonCorner::BottomLeft => DungeonPosition::new(X_MIN + 1, Y_MIN + 1),
-            DungeonCorner::BottomRight => DungeonPosition::new(X_MAX - 1, Y_MIN + 1),
-            DungeonCorner::TopLeft => DungeonPosition::new(X_MIN + 1, Y_MAX - 1),
-            DungeonCorner::TopRight => DungeonPosition::new(X_MAX - 1, Y_MAX - 1),
+            DungeonCorner::BottomLeft => DungeonPosition::new(x_min, y_min),
+            DungeonCorner::BottomRight => DungeonPosition::new(x_max, y_min),
+            DungeonCorner::TopLeft => DungeonPosition::new(x_min, y_max),
+            DungeonCorner::TopRight => DungeonPosition::new(x_max, y_max),
         }
     }
 }
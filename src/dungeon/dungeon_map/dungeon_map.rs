@@ -1,7 +1,7 @@
 use super::*;
+use crate::common::RandomGenerator;
 use bevy::prelude::Resource;
-
-const NUM_TILES: usize = MAP_WIDTH * MAP_HEIGHT;
+use rand::prelude::*;
 
 #[derive(Resource)]
 pub struct DungeonMap {
@@ -9,22 +9,37 @@ pub struct DungeonMap {
     /// or amulet.
     pub center: DungeonPosition,
     pub item_positions: Vec<DungeonPosition>,
+    /// Dungeon level (depth), starting at 1.
+    pub level: usize,
     pub monster_starting_positions: Vec<DungeonPosition>,
     pub player_starting_positions: Vec<DungeonPosition>,
+    size: MapSize,
     tiles: Vec<TileType>,
+    /// Where prefab vaults hold treasure (a rare item)
+    pub treasure_positions: Vec<DungeonPosition>,
 }
 
 impl DungeonMap {
-    pub fn new() -> Self {
+    /// A map of the given size with walls all around.
+    pub fn new(size: MapSize) -> Self {
         Self {
             center: DungeonPosition::new(0, 0),
             item_positions: vec![],
+            level: 1,
             monster_starting_positions: vec![],
             player_starting_positions: vec![],
-            tiles: vec![TileType::Wall; NUM_TILES],
+            size,
+            tiles: vec![TileType::Wall; size.num_tiles()],
+            treasure_positions: vec![],
         }
     }
 
+    /// The amulet is placed at the center of the deepest level (instead of the
+    /// exit).
+    pub fn amulet_position(&self) -> Option<DungeonPosition> {
+        (self.level == DEEPEST_LEVEL).then_some(self.center)
+    }
+
     /// Find the nearest floor tile to the given origin, within the given radius.
     /// If no floor tile is found within the radius, recursively search with an
     /// increased radius.
@@ -46,15 +61,43 @@ impl DungeonMap {
     }
 
     pub fn get_tile_type(&self, pos: &DungeonPosition) -> TileType {
-        self.tiles[MapPos::from(pos).to_idx()]
+        self.tiles[MapPos::new(pos, self.size).to_idx()]
+    }
+
+    /// Returns true if the position is within the dungeon and not a wall.
+    pub fn is_walkable(&self, pos: &DungeonPosition) -> bool {
+        self.is_valid_position(pos) && self.get_tile_type(pos) != TileType::Wall
     }
 
     pub fn is_valid_position(&self, pos: &DungeonPosition) -> bool {
-        MapPos::from(pos).is_valid()
+        MapPos::new(pos, self.size).is_valid()
+    }
+
+    /// The last step of every builder: place the exit (stairs down to the next
+    /// level) at the dungeon center and pick the item and then the monster spawn
+    /// points at random among the spawnable positions.
+    pub fn populate(
+        mut self,
+        num_items: usize,
+        num_monsters: usize,
+        rng: &mut RandomGenerator,
+    ) -> Self {
+        let center = self.center;
+        self.set_tile_type(&center, TileType::Exit);
+        self.item_positions = self.spawnable_positions().choose_multiple(rng, num_items);
+        self.monster_starting_positions = self
+            .spawnable_positions()
+            .choose_multiple(rng, num_monsters);
+
+        self
     }
 
     pub fn set_tile_type(&mut self, pos: &DungeonPosition, tile_type: TileType) {
-        self.tiles[MapPos::from(pos).to_idx()] = tile_type;
+        self.tiles[MapPos::new(pos, self.size).to_idx()] = tile_type;
+    }
+
+    pub fn size(&self) -> MapSize {
+        self.size
     }
 
     /// Returns an iterator over all spawnable positions for monsters and items.
@@ -68,10 +111,9 @@ impl DungeonMap {
     }
 
     pub fn tiles(&self) -> impl Iterator<Item = DungeonTile> + use<'_> {
-        self.tiles
-            .iter()
-            .enumerate()
-            .map(move |(idx, tile_type)| DungeonTile::new(self.idx_to_position(idx), *tile_type))
+        self.tiles.iter().enumerate().map(move |(idx, tile_type)| {
+            DungeonTile::new(MapPos::from_idx(idx, self.size), *tile_type)
+        })
     }
 
     fn far_from_players(&self, pos: DungeonPosition) -> bool {
@@ -79,44 +121,4 @@ impl DungeonMap {
             .iter()
             .all(|p| p.distance(pos).abs() > SAFETY_RADIUS)
     }
-
-    fn idx_to_position(&self, index: usize) -> DungeonPosition {
-        assert!(index < self.tiles.len());
-
-        let idx = index as isize;
-        const W: isize = MAP_WIDTH as isize;
-        const H: isize = MAP_HEIGHT as isize;
-        let x = (idx % W) - (W / 2);
-        let y = (idx / W) - (H / 2);
-
-        DungeonPosition::new(x, y)
-    }
-}
-
-/// A position in the dungeon map where the coordinate system origin is at the
-/// top left, unlike the DungeonPosition's coordinate system's origin which is
-/// at the center.
-#[derive(Clone, Copy)]
-struct MapPos {
-    pub x: usize,
-    pub y: usize,
-}
-
-impl MapPos {
-    pub fn is_valid(&self) -> bool {
-        self.x < MAP_WIDTH && self.y < MAP_HEIGHT
-    }
-
-    pub fn to_idx(&self) -> usize {
-        self.y * MAP_WIDTH + self.x
-    }
-}
-
-impl From<&DungeonPosition> for MapPos {
-    fn from(pos: &DungeonPosition) -> Self {
-        let x = (pos.x + (MAP_WIDTH as isize / 2)) as usize;
-        let y = (pos.y + (MAP_HEIGHT as isize / 2)) as usize;
-
-        Self { x, y }
-    }
 }
@@ -1,4 +1,4 @@
-use super::{MAP_Z_LAYER, X_MAX, X_MIN, Y_MAX, Y_MIN};
+use super::MAP_Z_LAYER;
 use bevy::math::{Vec2, Vec3};
 
 /// A position in the dungeon, represented as a pair of x and y coordinates.
@@ -27,11 +27,6 @@ impl DungeonPosition {
         Self { x, y }
     }
 
-    /// Returns true if the position is at the perimeter of the dungeon.
-    pub fn at_perimeter(&self) -> bool {
-        self.x == X_MAX || self.x == X_MIN || self.y == Y_MAX || self.y == Y_MIN
-    }
-
     pub fn distance(&self, other: Self) -> f32 {
         self.to_vec2().distance(other.to_vec2())
     }
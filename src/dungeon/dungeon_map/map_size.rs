@@ -0,0 +1,132 @@
+use super::{DungeonPosition, MAP_HEIGHT, MAP_WIDTH};
+use serde::Deserialize;
+
+/// The width and height of a dungeon map in tiles. The map is centered on the
+/// origin, so for an even width the x coordinates run from `-width / 2` to
+/// `width / 2 - 1` (and likewise for y).
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct MapSize {
+    pub height: usize,
+    pub width: usize,
+}
+
+impl MapSize {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { height, width }
+    }
+
+    /// Returns true if the position is at the perimeter of the dungeon.
+    pub fn at_perimeter(&self, pos: &DungeonPosition) -> bool {
+        pos.x == self.x_max()
+            || pos.x == self.x_min()
+            || pos.y == self.y_max()
+            || pos.y == self.y_min()
+    }
+
+    pub fn num_tiles(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn x_max(&self) -> isize {
+        (self.width - self.width / 2) as isize - 1
+    }
+
+    pub fn x_min(&self) -> isize {
+        -((self.width / 2) as isize)
+    }
+
+    pub fn y_max(&self) -> isize {
+        (self.height - self.height / 2) as isize - 1
+    }
+
+    pub fn y_min(&self) -> isize {
+        -((self.height / 2) as isize)
+    }
+}
+
+impl Default for MapSize {
+    fn default() -> Self {
+        Self::new(MAP_WIDTH, MAP_HEIGHT)
+    }
+}
+
+/// A position in the dungeon map where the coordinate system origin is at the
+/// bottom left, unlike the DungeonPosition's coordinate system's origin which is
+/// at the center.
+#[derive(Clone, Copy)]
+pub(super) struct MapPos {
+    size: MapSize,
+    pub x: usize,
+    pub y: usize,
+}
+
+impl MapPos {
+    pub fn new(pos: &DungeonPosition, size: MapSize) -> Self {
+        let x = (pos.x - size.x_min()) as usize;
+        let y = (pos.y - size.y_min()) as usize;
+
+        Self { size, x, y }
+    }
+
+    /// The position of the tile with the given index.
+    pub fn from_idx(idx: usize, size: MapSize) -> DungeonPosition {
+        assert!(idx < size.num_tiles());
+
+        DungeonPosition::new(
+            (idx % size.width) as isize + size.x_min(),
+            (idx / size.width) as isize + size.y_min(),
+        )
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.x < self.size.width && self.y < self.size.height
+    }
+
+    pub fn to_idx(self) -> usize {
+        self.y * self.size.width + self.x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::even(MapSize::new(100, 52), (-50, 49), (-26, 25))]
+    #[case::odd(MapSize::new(21, 15), (-10, 10), (-7, 7))]
+    fn bounds(
+        #[case] size: MapSize,
+        #[case] x_bounds: (isize, isize),
+        #[case] y_bounds: (isize, isize),
+    ) {
+        assert_eq!((size.x_min(), size.x_max()), x_bounds);
+        assert_eq!((size.y_min(), size.y_max()), y_bounds);
+        assert_eq!(
+            ((size.x_max() - size.x_min() + 1) * (size.y_max() - size.y_min() + 1)) as usize,
+            size.num_tiles()
+        );
+    }
+
+    #[rstest]
+    #[case::default(MapSize::default())]
+    #[case::small(MapSize::new(9, 7))]
+    fn idx_round_trip(#[case] size: MapSize) {
+        for idx in 0..size.num_tiles() {
+            let pos = MapPos::from_idx(idx, size);
+            let map_pos = MapPos::new(&pos, size);
+
+            assert!(map_pos.is_valid());
+            assert_eq!(map_pos.to_idx(), idx);
+        }
+    }
+
+    #[test]
+    fn outside_map() {
+        let size = MapSize::new(9, 7);
+
+        assert!(!MapPos::new(&DungeonPosition::new(5, 0), size).is_valid());
+        assert!(!MapPos::new(&DungeonPosition::new(0, -4), size).is_valid());
+    }
+}
@@ -0,0 +1,24 @@
+use super::{NUM_ITEMS, NUM_MONSTERS, NUM_ROOMS, ROOM_MAX_HEIGHT, ROOM_MAX_WIDTH};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct RandomRoomsConfig {
+    pub num_items: usize,
+    pub num_monsters: usize,
+    pub num_rooms: usize,
+    pub room_max_height: usize,
+    pub room_max_width: usize,
+}
+
+impl Default for RandomRoomsConfig {
+    fn default() -> Self {
+        Self {
+            num_items: NUM_ITEMS,
+            num_monsters: NUM_MONSTERS,
+            num_rooms: NUM_ROOMS,
+            room_max_height: ROOM_MAX_HEIGHT,
+            room_max_width: ROOM_MAX_WIDTH,
+        }
+    }
+}
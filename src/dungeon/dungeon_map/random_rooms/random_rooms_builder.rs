@@ -1,55 +1,53 @@
 use super::*;
 use crate::prelude::*;
-use rand::prelude::*;
+
+/// Attempts at placing a room that does not overlap the others, per room
+const ROOM_ATTEMPTS: usize = 100;
 
 pub struct RandomRoomsBuilder {
+    config: RandomRoomsConfig,
     map: DungeonMap,
     rooms: Vec<Room>,
 }
 
-impl RandomRoomsBuilder {
-    pub fn build(rng: &mut RandomGenerator) -> DungeonMap {
+impl DungeonBuilder for RandomRoomsBuilder {
+    type Config = RandomRoomsConfig;
+
+    fn build(
+        config: &RandomRoomsConfig,
+        size: MapSize,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> DungeonMap {
         info!("Building random rooms dungeon.");
+        assert!(
+            size.width > config.room_max_width + 2 && size.height > config.room_max_height + 2,
+            "Map size {size:?} is too small for the random rooms"
+        );
+
         Self {
-            map: DungeonMap::new(),
+            config: config.clone(),
+            map: DungeonMap::new(size),
             rooms: vec![],
         }
         .create_rooms(rng)
         .build_corridors(rng)
         .set_center()
-        .add_player_starting_positions()
-        .add_items(rng)
-        .add_monster_starting_positions(rng)
+        .add_player_starting_positions(num_players)
         .map
+        .populate(config.num_items, config.num_monsters, rng)
     }
+}
 
-    fn add_items(mut self, rng: &mut RandomGenerator) -> Self {
-        self.map.item_positions = self
-            .map
-            .spawnable_positions()
-            .choose_multiple(rng, NUM_ITEMS);
-
-        self
-    }
-
-    fn add_monster_starting_positions(mut self, rng: &mut RandomGenerator) -> Self {
-        self.map.monster_starting_positions = self
-            .map
-            .spawnable_positions()
-            .choose_multiple(rng, NUM_MONSTERS);
-
-        self
-    }
-
-    fn add_player_starting_positions(mut self) -> Self {
-        self.map
-            .player_starting_positions
-            .push(self.rooms[0].center());
-        if config::GAME_MODE != GameMode::SinglePlayer {
-            self.map
-                .player_starting_positions
-                .push(self.rooms[1].center());
-        }
+impl RandomRoomsBuilder {
+    /// Each player starts at the center of a different room.
+    fn add_player_starting_positions(mut self, num_players: usize) -> Self {
+        self.map.player_starting_positions = self
+            .rooms
+            .iter()
+            .take(num_players)
+            .map(|room| room.center())
+            .collect();
 
         self
     }
@@ -74,8 +72,18 @@ impl RandomRoomsBuilder {
         self
     }
 
+    /// Create the configured number of rooms, or as many as fit if the map is
+    /// too small.
     fn create_rooms(mut self, rng: &mut RandomGenerator) -> Self {
-        while self.rooms.len() < NUM_ROOMS {
+        let num_rooms = self.config.num_rooms;
+        let mut attempts = 0;
+        while self.rooms.len() < num_rooms {
+            attempts += 1;
+            if attempts > num_rooms * ROOM_ATTEMPTS {
+                warn!("Only {} of {num_rooms} rooms fit", self.rooms.len());
+                break;
+            }
+
             let room = self.create_room(rng);
 
             if !self.rooms.iter().any(|r| r.overlaps(&room)) {
@@ -88,7 +96,7 @@ impl RandomRoomsBuilder {
                     });
 
                 info!(
-                    "Room {} of {NUM_ROOMS} generated: {room:?}",
+                    "Room {} of {num_rooms} generated: {room:?}",
                     self.rooms.len()
                 );
                 self.rooms.push(room);
@@ -101,16 +109,22 @@ impl RandomRoomsBuilder {
     }
 
     fn create_room(&self, rng: &mut RandomGenerator) -> Room {
-        const ROOM_X_MAX: isize = X_MAX - (ROOM_MAX_WIDTH as isize);
-        const ROOM_X_MIN: isize = X_MIN + 1;
-        const ROOM_Y_MAX: isize = Y_MAX - (ROOM_MAX_HEIGHT as isize);
-        const ROOM_Y_MIN: isize = Y_MIN + 1;
+        let RandomRoomsConfig {
+            room_max_height,
+            room_max_width,
+            ..
+        } = self.config;
+        let size = self.map.size();
+        let room_x_max = size.x_max() - (room_max_width as isize);
+        let room_x_min = size.x_min() + 1;
+        let room_y_max = size.y_max() - (room_max_height as isize);
+        let room_y_min = size.y_min() + 1;
 
         Room::new(
-            rng.gen_range(ROOM_X_MIN..ROOM_X_MAX),
-            rng.gen_range(ROOM_Y_MIN..ROOM_Y_MAX),
-            rng.gen_range(2..ROOM_MAX_WIDTH),
-            rng.gen_range(2..ROOM_MAX_HEIGHT),
+            rng.gen_range(room_x_min..room_x_max),
+            rng.gen_range(room_y_min..room_y_max),
+            rng.gen_range(2..room_max_width),
+            rng.gen_range(2..room_max_height),
         )
     }
 
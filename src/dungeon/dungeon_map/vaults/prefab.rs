@@ -0,0 +1,110 @@
+use super::DungeonPosition;
+use bevy::prelude::*;
+use serde::{de::Error, Deserialize, Deserializer};
+
+#[derive(Asset, Deserialize, TypePath)]
+pub struct PrefabData {
+    pub prefabs: Vec<Prefab>,
+}
+
+/// A hand-made room layout authored as ASCII rows (top row first):
+/// `#` wall, `.` floor, `M` monster spawn, `!` item spawn and `$` treasure.
+/// All spawn points are floor tiles. Any other character fails the asset load.
+#[derive(Debug, Deserialize)]
+pub struct Prefab {
+    pub name: String,
+    #[serde(deserialize_with = "deserialize_rows")]
+    pub rows: Vec<Vec<PrefabTile>>,
+}
+
+impl Prefab {
+    pub fn height(&self) -> isize {
+        self.rows.len() as isize
+    }
+
+    pub fn width(&self) -> isize {
+        self.rows.iter().map(|r| r.len()).max().unwrap_or(0) as isize
+    }
+
+    /// Returns the dungeon position and tile of each prefab tile when the
+    /// prefab's top left corner is placed at the given origin.
+    pub fn tiles(
+        &self,
+        origin: DungeonPosition,
+    ) -> impl Iterator<Item = (DungeonPosition, PrefabTile)> + use<'_> {
+        self.rows.iter().enumerate().flat_map(move |(row, line)| {
+            line.iter().enumerate().map(move |(col, tile)| {
+                let pos = DungeonPosition::new(origin.x + col as isize, origin.y - row as isize);
+
+                (pos, *tile)
+            })
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrefabTile {
+    Floor,
+    Item,
+    Monster,
+    Treasure,
+    Wall,
+}
+
+impl PrefabTile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Floor),
+            '!' => Some(Self::Item),
+            'M' => Some(Self::Monster),
+            '$' => Some(Self::Treasure),
+            '#' => Some(Self::Wall),
+            _ => None,
+        }
+    }
+}
+
+fn deserialize_rows<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Vec<PrefabTile>>, D::Error> {
+    Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .map(|row| {
+            row.chars()
+                .map(|c| {
+                    PrefabTile::from_char(c).ok_or_else(|| {
+                        D::Error::custom(format!("Invalid character '{c}' in prefab row \"{row}\""))
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::scene::ron;
+
+    #[test]
+    fn load() {
+        let prefab: Prefab = ron::from_str(r##"(name: "test", rows: ["#M", "$."])"##).unwrap();
+
+        assert_eq!(
+            prefab.rows,
+            vec![
+                vec![PrefabTile::Wall, PrefabTile::Monster],
+                vec![PrefabTile::Treasure, PrefabTile::Floor]
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_character() {
+        let error = ron::from_str::<Prefab>(r##"(name: "test", rows: ["#.", "#x"])"##).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("Invalid character 'x' in prefab row \"#x\""));
+    }
+}
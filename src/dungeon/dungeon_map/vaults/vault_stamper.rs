@@ -0,0 +1,315 @@
+use super::*;
+use crate::prelude::*;
+
+/// Stamps a random prefab vault into an already built dungeon (by any of the
+/// builders) at a spot where it fits, adding its spawn points to the map.
+pub struct VaultStamper<'a> {
+    map: &'a mut DungeonMap,
+    prefab: &'a Prefab,
+}
+
+impl<'a> VaultStamper<'a> {
+    /// Try random spots until the prefab fits and the players can still reach
+    /// the dungeon center (and the vault). Spawn points cut off by the vault are
+    /// dropped. Returns false if no spot was found, leaving the map untouched.
+    pub fn stamp(
+        map: &'a mut DungeonMap,
+        prefabs: &'a [Prefab],
+        rng: &mut RandomGenerator,
+    ) -> bool {
+        if prefabs.is_empty() {
+            return false;
+        }
+
+        let prefab = &prefabs[rng.gen_range(0..prefabs.len())];
+        let mut stamper = Self { map, prefab };
+
+        for _ in 0..VAULT_PLACEMENT_ATTEMPTS {
+            let origin = stamper.random_origin(rng);
+            if stamper.try_stamp(origin) {
+                info!("Stamped vault {} at {origin}", prefab.name);
+                return true;
+            }
+        }
+
+        warn!("No spot found for vault {}", prefab.name);
+        false
+    }
+
+    /// The prefab fits if it is within the perimeter walls, does not cover the
+    /// dungeon center (exit or amulet) and is outside the players' safety radius.
+    fn fits(&self, origin: DungeonPosition) -> bool {
+        self.prefab.tiles(origin).all(|(pos, _)| {
+            self.map.is_valid_position(&pos)
+                && !self.map.size().at_perimeter(&pos)
+                && pos != self.map.center
+                && self
+                    .map
+                    .player_starting_positions
+                    .iter()
+                    .all(|p| p.distance(pos) > SAFETY_RADIUS)
+        })
+    }
+
+    /// Top left corner such that the whole prefab is within the perimeter walls
+    fn random_origin(&self, rng: &mut RandomGenerator) -> DungeonPosition {
+        let (width, height) = (self.prefab.width(), self.prefab.height());
+        let size = self.map.size();
+        let (x_min, x_max) = (size.x_min(), size.x_max());
+        let (y_min, y_max) = (size.y_min(), size.y_max());
+
+        DungeonPosition::new(
+            rng.gen_range(x_min + 1..(x_max - width + 1).max(x_min + 2)),
+            rng.gen_range((y_min + height).min(y_max - 1)..y_max),
+        )
+    }
+
+    /// Ensure all players can reach the center (the exit or amulet) and every
+    /// walkable tile of the vault can be reached from there.
+    fn reachable(&self, origin: DungeonPosition) -> bool {
+        self.map
+            .player_starting_positions
+            .iter()
+            .all(|p| self.reaches_center(*p))
+            && self
+                .prefab
+                .tiles(origin)
+                .filter(|(_, tile)| *tile != PrefabTile::Wall)
+                .all(|(pos, _)| self.reaches_center(pos))
+    }
+
+    fn reaches_center(&self, pos: DungeonPosition) -> bool {
+        AStarPathFinder::find(pos, self.map.center, self.map).path_found()
+    }
+
+    /// The vault walls may cut off spawn points the builder placed around it.
+    fn remove_unreachable_spawn_points(&mut self) {
+        let reachable = |positions: &[DungeonPosition]| -> Vec<DungeonPosition> {
+            positions
+                .iter()
+                .copied()
+                .filter(|p| self.reaches_center(*p))
+                .collect()
+        };
+        let item_positions = reachable(&self.map.item_positions);
+        let monster_starting_positions = reachable(&self.map.monster_starting_positions);
+        let treasure_positions = reachable(&self.map.treasure_positions);
+
+        self.map.item_positions = item_positions;
+        self.map.monster_starting_positions = monster_starting_positions;
+        self.map.treasure_positions = treasure_positions;
+    }
+
+    /// Replace the spawn points within the vault with those of the prefab.
+    fn replace_spawn_points(&mut self, origin: DungeonPosition) {
+        let footprint: Vec<DungeonPosition> = self.prefab.tiles(origin).map(|(p, _)| p).collect();
+        self.map.item_positions.retain(|p| !footprint.contains(p));
+        self.map
+            .monster_starting_positions
+            .retain(|p| !footprint.contains(p));
+
+        for (pos, tile) in self.prefab.tiles(origin) {
+            match tile {
+                PrefabTile::Item => self.map.item_positions.push(pos),
+                PrefabTile::Monster => self.map.monster_starting_positions.push(pos),
+                PrefabTile::Treasure => self.map.treasure_positions.push(pos),
+                PrefabTile::Floor | PrefabTile::Wall => {}
+            }
+        }
+    }
+
+    fn try_stamp(&mut self, origin: DungeonPosition) -> bool {
+        if !self.fits(origin) {
+            return false;
+        }
+
+        let prior: Vec<(DungeonPosition, TileType)> = self
+            .prefab
+            .tiles(origin)
+            .map(|(pos, _)| (pos, self.map.get_tile_type(&pos)))
+            .collect();
+
+        for (pos, tile) in self.prefab.tiles(origin) {
+            let tile_type = match tile {
+                PrefabTile::Wall => TileType::Wall,
+                _ => TileType::Floor,
+            };
+            self.map.set_tile_type(&pos, tile_type);
+        }
+
+        if !self.reachable(origin) {
+            prior
+                .iter()
+                .for_each(|(pos, tile_type)| self.map.set_tile_type(pos, *tile_type));
+
+            return false;
+        }
+
+        self.replace_spawn_points(origin);
+        self.remove_unreachable_spawn_points();
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::scene::ron;
+
+    #[test]
+    fn stamp_vault() {
+        let mut map = create_open_map();
+        let prefabs = vec![create_prefab()];
+        let mut rng = RandomGenerator::new();
+
+        assert!(VaultStamper::stamp(&mut map, &prefabs, &mut rng));
+
+        assert_eq!(map.treasure_positions.len(), 1);
+        let treasure = map.treasure_positions[0];
+        assert_eq!(map.get_tile_type(&treasure), TileType::Floor);
+        assert!(map
+            .monster_starting_positions
+            .contains(&DungeonPosition::new(treasure.x - 1, treasure.y)));
+        assert!(map
+            .item_positions
+            .contains(&DungeonPosition::new(treasure.x + 1, treasure.y)));
+        assert_eq!(
+            map.get_tile_type(&DungeonPosition::new(treasure.x, treasure.y + 1)),
+            TileType::Wall
+        );
+    }
+
+    #[test]
+    fn spawn_points_within_vault_replaced() {
+        let mut map = create_open_map();
+        let prefab = create_prefab();
+        let origin = DungeonPosition::new(-10, 10);
+        // the vault and the tiles around it
+        map.item_positions = (origin.x - 1..=origin.x + prefab.width())
+            .flat_map(|x| (origin.y - prefab.height()..=origin.y + 1).map(move |y| (x, y)))
+            .map(|(x, y)| DungeonPosition::new(x, y))
+            .collect();
+        let item_count = map.item_positions.len();
+        let mut stamper = VaultStamper {
+            map: &mut map,
+            prefab: &prefab,
+        };
+
+        assert!(stamper.try_stamp(origin));
+
+        // 15 tiles replaced by a single item spawn point
+        assert_eq!(map.item_positions.len(), item_count - 15 + 1);
+    }
+
+    #[test]
+    fn vault_does_not_cover_center_or_player() {
+        let mut map = create_open_map();
+        let prefabs = vec![create_prefab()];
+        let mut rng = RandomGenerator::new();
+
+        VaultStamper::stamp(&mut map, &prefabs, &mut rng);
+
+        let player_pos = map.player_starting_positions[0];
+        assert_eq!(map.get_tile_type(&map.center), TileType::Exit);
+        assert_eq!(map.get_tile_type(&player_pos), TileType::Floor);
+        assert!(AStarPathFinder::find(player_pos, map.center, &map).path_found());
+    }
+
+    #[test]
+    fn no_spot_found() {
+        // a vault surrounded by walls cannot be reached from the center
+        let mut map = DungeonMap::new(MapSize::default());
+        map.player_starting_positions
+            .push(DungeonPosition::new(0, 0));
+        map.center = DungeonPosition::new(0, 0);
+        let prefabs = vec![create_prefab()];
+        let mut rng = RandomGenerator::new();
+
+        let stamped = VaultStamper::stamp(&mut map, &prefabs, &mut rng);
+
+        assert!(!stamped);
+        assert!(map.tiles().all(|t| t.tile_type == TileType::Wall));
+    }
+
+    #[test]
+    fn enclosed_treasure_rejected() {
+        let mut map = create_open_map();
+        // the floor can be reached but the treasure is walled in
+        let prefab = create_prefab_from_rows(&[".###", "##$#", "####"]);
+        let origin = DungeonPosition::new(-10, 10);
+        let mut stamper = VaultStamper {
+            map: &mut map,
+            prefab: &prefab,
+        };
+
+        assert!(!stamper.try_stamp(origin));
+
+        assert!(map.treasure_positions.is_empty());
+        assert_eq!(map.get_tile_type(&origin), TileType::Floor);
+        assert_eq!(
+            map.get_tile_type(&DungeonPosition::new(-8, 9)),
+            TileType::Floor
+        );
+    }
+
+    #[test]
+    fn cut_off_spawn_points_removed() {
+        let mut map = create_open_map();
+        // a pocket in the top left corner, only open to the right
+        let (x_min, y_max) = (map.size().x_min(), map.size().y_max());
+        let pocket = DungeonPosition::new(x_min + 1, y_max - 1);
+        map.set_tile_type(&DungeonPosition::new(x_min + 1, y_max - 2), TileType::Wall);
+        map.item_positions.push(pocket);
+        map.monster_starting_positions.push(pocket);
+        map.treasure_positions.push(pocket);
+        let prefab = create_prefab_from_rows(&["###"]);
+        let mut stamper = VaultStamper {
+            map: &mut map,
+            prefab: &prefab,
+        };
+
+        // the vault walls close the pocket
+        assert!(stamper.try_stamp(DungeonPosition::new(x_min + 2, y_max - 1)));
+
+        assert!(map.item_positions.is_empty());
+        assert!(map.monster_starting_positions.is_empty());
+        assert!(map.treasure_positions.is_empty());
+    }
+
+    /// A vault with a single opening on its bottom side
+    fn create_prefab() -> Prefab {
+        create_prefab_from_rows(&["#####", "#M$!#", "##.##"])
+    }
+
+    fn create_prefab_from_rows(rows: &[&str]) -> Prefab {
+        let rows = rows
+            .iter()
+            .map(|row| format!("\"{row}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        ron::from_str(&format!("(name: \"test\", rows: [{rows}])")).unwrap()
+    }
+
+    /// Floor everywhere within the perimeter with the exit at the center and
+    /// a player in the bottom left corner.
+    fn create_open_map() -> DungeonMap {
+        let mut map = DungeonMap::new(MapSize::default());
+        map.tiles()
+            .filter(|t| !MapSize::default().at_perimeter(&t.pos))
+            .map(|t| t.pos)
+            .collect::<Vec<_>>()
+            .iter()
+            .for_each(|pos| map.set_tile_type(pos, TileType::Floor));
+        let center = DungeonPosition::new(0, 0);
+        map.center = center;
+        map.set_tile_type(&center, TileType::Exit);
+        map.player_starting_positions.push(DungeonPosition::new(
+            map.size().x_min() + 1,
+            map.size().y_min() + 1,
+        ));
+
+        map
+    }
+}
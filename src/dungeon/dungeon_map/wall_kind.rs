@@ -0,0 +1,103 @@
+use super::{DungeonMap, DungeonPosition, TileType};
+use bevy::prelude::Component;
+
+/// Autotile variant of a wall tile, derived from its neighbors in the
+/// [`DungeonMap`] so the shape of rooms and caves can be read on screen.
+/// Only walls bordering a walkable tile (including diagonally) are ever seen,
+/// so only those connect to their neighbors. The rest are `Interior`.
+#[derive(Clone, Component, Copy, Debug, Eq, PartialEq)]
+pub enum WallKind {
+    /// Joins walls both horizontally and vertically (including T-junctions)
+    Corner,
+    Horizontal,
+    /// Not adjacent to any walkable tile, so never revealed
+    Interior,
+    /// Stands alone with no wall connected above, below or to either side
+    Pillar,
+    Vertical,
+}
+
+impl WallKind {
+    /// Determine the variant of the wall at the given position.
+    pub fn new(pos: &DungeonPosition, dungeon: &DungeonMap) -> Self {
+        if !Self::borders_walkable(pos, dungeon) {
+            return Self::Interior;
+        }
+
+        let connects = |dx: isize, dy: isize| {
+            let neighbor = DungeonPosition::new(pos.x + dx, pos.y + dy);
+            dungeon.is_valid_position(&neighbor)
+                && dungeon.get_tile_type(&neighbor) == TileType::Wall
+                && Self::borders_walkable(&neighbor, dungeon)
+        };
+        let horizontal = connects(-1, 0) || connects(1, 0);
+        let vertical = connects(0, -1) || connects(0, 1);
+
+        match (horizontal, vertical) {
+            (false, false) => Self::Pillar,
+            (false, true) => Self::Vertical,
+            (true, false) => Self::Horizontal,
+            (true, true) => Self::Corner,
+        }
+    }
+
+    /// Returns true if any of the 8 surrounding tiles is walkable.
+    fn borders_walkable(pos: &DungeonPosition, dungeon: &DungeonMap) -> bool {
+        pos.perimeter(1)
+            .any(|neighbor| dungeon.is_walkable(&neighbor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::MapSize, *};
+    use rstest::rstest;
+
+    /// A 5x4 room spanning x -2..=2 and y -2..=1, surrounded by walls
+    #[rstest]
+    #[case::top_edge(0, 2, WallKind::Horizontal)]
+    #[case::bottom_edge(1, -3, WallKind::Horizontal)]
+    #[case::left_edge(-3, 0, WallKind::Vertical)]
+    #[case::right_edge(3, -1, WallKind::Vertical)]
+    #[case::top_left_corner(-3, 2, WallKind::Corner)]
+    #[case::bottom_right_corner(3, -3, WallKind::Corner)]
+    #[case::interior(0, 4, WallKind::Interior)]
+    fn room_walls(#[case] x: isize, #[case] y: isize, #[case] expected: WallKind) {
+        let dungeon = create_room();
+
+        let kind = WallKind::new(&DungeonPosition::new(x, y), &dungeon);
+
+        assert_eq!(kind, expected);
+    }
+
+    #[test]
+    fn pillar() {
+        let mut dungeon = create_room();
+        let pos = DungeonPosition::new(0, 0);
+        dungeon.set_tile_type(&pos, TileType::Wall);
+
+        assert_eq!(WallKind::new(&pos, &dungeon), WallKind::Pillar);
+    }
+
+    #[test]
+    fn wall_at_dungeon_edge() {
+        let mut dungeon = DungeonMap::new(MapSize::default());
+        let floor = DungeonPosition::new(0, -25);
+        dungeon.set_tile_type(&floor, TileType::Floor);
+
+        let kind = WallKind::new(&DungeonPosition::new(0, -26), &dungeon);
+
+        assert_eq!(kind, WallKind::Horizontal);
+    }
+
+    fn create_room() -> DungeonMap {
+        let mut dungeon = DungeonMap::new(MapSize::default());
+        for x in -2..=2 {
+            for y in -2..=1 {
+                dungeon.set_tile_type(&DungeonPosition::new(x, y), TileType::Floor);
+            }
+        }
+
+        dungeon
+    }
+}
@@ -0,0 +1,31 @@
+use super::{NUM_ITEMS, NUM_MONSTERS, WFC_MAX_ATTEMPTS, WFC_PATTERN_SIZE};
+use serde::Deserialize;
+
+/// Hand-drawn sample of rooms and corridors, shared with `assets/builders.ron`
+/// which leaves its `sample` to this default.
+const SAMPLE: &str = include_str!("../../../../assets/wfc_sample.txt");
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct WfcConfig {
+    /// Collapse attempts before falling back to the cellular automata builder
+    pub max_attempts: usize,
+    pub num_items: usize,
+    pub num_monsters: usize,
+    /// Width and height of the tile patterns learned from the sample
+    pub pattern_size: usize,
+    /// Sample map rows (top row first): '#' wall and '.' floor
+    pub sample: Vec<String>,
+}
+
+impl Default for WfcConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: WFC_MAX_ATTEMPTS,
+            num_items: NUM_ITEMS,
+            num_monsters: NUM_MONSTERS,
+            pattern_size: WFC_PATTERN_SIZE,
+            sample: SAMPLE.lines().map(str::to_string).collect(),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use super::TileType;
+
+/// Offsets (column, row) of the left, right, upper and lower neighbors of a
+/// cell, with rows running down.
+pub(super) const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// The direction pointing back, given the order of [`DIRECTIONS`].
+pub(super) fn opposite(direction: usize) -> usize {
+    direction ^ 1
+}
+
+/// The distinct square tile patterns of a sample map, how often each occurs and
+/// which patterns may neighbor each other.
+pub(super) struct Patterns {
+    /// By direction and then pattern, the patterns that may be placed at that
+    /// offset from the pattern, i.e. agree with it where they overlap.
+    pub neighbors: [Vec<Vec<usize>>; 4],
+    /// Width and height of each pattern
+    pub size: usize,
+    /// Tiles of each pattern, row by row
+    pub tiles: Vec<Vec<TileType>>,
+    /// Occurrences of each pattern in the sample
+    pub weights: Vec<u32>,
+}
+
+impl Patterns {
+    /// Learn the patterns of the sample rows (top row first), where `#` is a
+    /// wall and `.` a floor. The sample wraps around at its edges so every
+    /// pattern has neighbors in all directions.
+    pub fn learn(sample: &[String], size: usize) -> Self {
+        let grid = sample
+            .iter()
+            .map(|row| row.chars().map(tile_type).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let height = grid.len();
+        let width = grid.first().map_or(0, Vec::len);
+        assert!(
+            size > 0 && width >= size && height >= size && grid.iter().all(|r| r.len() == width),
+            "WFC sample must be a rectangle of at least {size}x{size} tiles"
+        );
+
+        let mut tiles: Vec<Vec<TileType>> = vec![];
+        let mut weights = vec![];
+        for row in 0..height {
+            for col in 0..width {
+                let pattern = (0..size * size)
+                    .map(|i| grid[(row + i / size) % height][(col + i % size) % width])
+                    .collect::<Vec<_>>();
+
+                match tiles.iter().position(|p| *p == pattern) {
+                    Some(idx) => weights[idx] += 1,
+                    None => {
+                        tiles.push(pattern);
+                        weights.push(1);
+                    }
+                }
+            }
+        }
+
+        let neighbors = DIRECTIONS.map(|(dx, dy)| {
+            tiles
+                .iter()
+                .map(|first| {
+                    (0..tiles.len())
+                        .filter(|&q| agrees(first, &tiles[q], size, dx, dy))
+                        .collect()
+                })
+                .collect()
+        });
+
+        Self {
+            neighbors,
+            size,
+            tiles,
+            weights,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+}
+
+/// Whether the second pattern, when offset by the given columns and rows from
+/// the first, has the same tiles where the two overlap.
+fn agrees(first: &[TileType], second: &[TileType], size: usize, dx: isize, dy: isize) -> bool {
+    let size = size as isize;
+    let idx = |col: isize, row: isize| (row * size + col) as usize;
+
+    (0..size)
+        .flat_map(|row| (0..size).map(move |col| (col, row)))
+        .filter(|&(col, row)| (0..size).contains(&(col - dx)) && (0..size).contains(&(row - dy)))
+        .all(|(col, row)| first[idx(col, row)] == second[idx(col - dx, row - dy)])
+}
+
+fn tile_type(c: char) -> TileType {
+    match c {
+        '#' => TileType::Wall,
+        '.' => TileType::Floor,
+        _ => panic!("Invalid character '{c}' in WFC sample"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn learn_patterns() {
+        let sample = ["##", "#."].map(String::from);
+
+        let patterns = Patterns::learn(&sample, 2);
+
+        // wrapping around, each 2x2 window has the floor at a different corner
+        assert_eq!(patterns.len(), 4);
+        assert_eq!(patterns.weights, vec![1; 4]);
+    }
+
+    #[test]
+    fn neighbors_agree_on_overlap() {
+        let sample = ["###", "#..", "#.."].map(String::from);
+
+        let patterns = Patterns::learn(&sample, 2);
+
+        for (direction, (dx, dy)) in DIRECTIONS.into_iter().enumerate() {
+            for (p, neighbors) in patterns.neighbors[direction].iter().enumerate() {
+                assert!(!neighbors.is_empty(), "pattern {p} has no neighbor");
+                for &q in neighbors {
+                    assert!(agrees(&patterns.tiles[p], &patterns.tiles[q], 2, dx, dy));
+                    assert!(patterns.neighbors[opposite(direction)][q].contains(&p));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid character 'x' in WFC sample")]
+    fn invalid_sample() {
+        Patterns::learn(&["#x".to_string(), "..".to_string()], 2);
+    }
+}
@@ -0,0 +1,228 @@
+use super::{opposite, Patterns, TileType, DIRECTIONS};
+use crate::prelude::*;
+
+/// Wave function collapse of a grid of cells, each of which starts out allowing
+/// any pattern. The cell with the fewest options is collapsed to a single
+/// pattern (picked by weight) and the patterns that no longer agree with it are
+/// removed from the other cells, until all cells are collapsed or one runs out
+/// of options (a contradiction).
+/// All choices use integer random numbers, so the result only depends on the
+/// state of the [`RandomGenerator`].
+pub(super) struct Wave<'a> {
+    /// By cell and pattern, and for each direction, the number of patterns
+    /// still allowed at the neighbor in the opposite direction that agree with
+    /// the pattern. The pattern is removed once any count drops to zero.
+    compatible: Vec<[usize; 4]>,
+    contradiction: bool,
+    columns: usize,
+    /// Number of patterns still allowed per cell
+    options: Vec<usize>,
+    patterns: &'a Patterns,
+    /// By cell and pattern, whether the pattern is still allowed
+    possible: Vec<bool>,
+    /// Removed cell patterns whose neighbors are yet to be updated
+    removed: Vec<(usize, usize)>,
+    rows: usize,
+}
+
+impl<'a> Wave<'a> {
+    /// Collapse a grid of the given size in tiles. Returns its tiles row by row
+    /// (top row first), or None on a contradiction.
+    pub fn collapse(
+        patterns: &'a Patterns,
+        width: usize,
+        height: usize,
+        rng: &mut RandomGenerator,
+    ) -> Option<Vec<TileType>> {
+        let mut wave = Self::new(patterns, width, height);
+
+        while let Some(cell) = wave.lowest_options_cell(rng) {
+            wave.collapse_cell(cell, rng);
+            wave.propagate();
+            if wave.contradiction {
+                return None;
+            }
+        }
+
+        Some(wave.tiles(width, height))
+    }
+
+    /// Each cell is the top left corner of a pattern, so the last cells are a
+    /// pattern's size from the right and bottom edges.
+    fn new(patterns: &'a Patterns, width: usize, height: usize) -> Self {
+        assert!(width >= patterns.size && height >= patterns.size);
+        let columns = width - patterns.size + 1;
+        let rows = height - patterns.size + 1;
+        let cells = columns * rows;
+
+        let counts = (0..patterns.len())
+            .map(|p| std::array::from_fn(|d| patterns.neighbors[opposite(d)][p].len()))
+            .collect::<Vec<_>>();
+
+        Self {
+            compatible: counts.repeat(cells),
+            contradiction: false,
+            columns,
+            options: vec![patterns.len(); cells],
+            patterns,
+            possible: vec![true; cells * patterns.len()],
+            removed: vec![],
+            rows,
+        }
+    }
+
+    /// Keep a single pattern in the cell, picked at random by weight.
+    fn collapse_cell(&mut self, cell: usize, rng: &mut RandomGenerator) {
+        let allowed = (0..self.patterns.len())
+            .filter(|&p| self.possible[self.idx(cell, p)])
+            .collect::<Vec<_>>();
+        let total = allowed
+            .iter()
+            .map(|&p| self.patterns.weights[p])
+            .sum::<u32>();
+
+        let mut roll = rng.gen_range(0..total);
+        let chosen = *allowed
+            .iter()
+            .find(|&&p| {
+                let weight = self.patterns.weights[p];
+                if roll < weight {
+                    return true;
+                }
+                roll -= weight;
+                false
+            })
+            .expect("Roll exceeds total weight");
+
+        allowed
+            .into_iter()
+            .filter(|&p| p != chosen)
+            .for_each(|p| self.remove(cell, p));
+    }
+
+    fn idx(&self, cell: usize, pattern: usize) -> usize {
+        cell * self.patterns.len() + pattern
+    }
+
+    /// A random one of the uncollapsed cells with the fewest patterns left, if
+    /// any.
+    fn lowest_options_cell(&self, rng: &mut RandomGenerator) -> Option<usize> {
+        let fewest = self.options.iter().copied().filter(|&o| o > 1).min()?;
+        let cells = (0..self.options.len())
+            .filter(|&cell| self.options[cell] == fewest)
+            .collect::<Vec<_>>();
+
+        Some(cells[rng.gen_range(0..cells.len())])
+    }
+
+    fn neighbor(&self, cell: usize, direction: usize) -> Option<usize> {
+        let (dx, dy) = DIRECTIONS[direction];
+        let column = (cell % self.columns).checked_add_signed(dx)?;
+        let row = (cell / self.columns).checked_add_signed(dy)?;
+
+        (column < self.columns && row < self.rows).then_some(row * self.columns + column)
+    }
+
+    /// Remove the patterns that no longer agree with any pattern allowed in a
+    /// neighboring cell, and so on for their neighbors.
+    fn propagate(&mut self) {
+        let patterns = self.patterns;
+        while let Some((cell, pattern)) = self.removed.pop() {
+            for direction in 0..DIRECTIONS.len() {
+                let Some(neighbor) = self.neighbor(cell, direction) else {
+                    continue;
+                };
+
+                for &other in &patterns.neighbors[direction][pattern] {
+                    let idx = self.idx(neighbor, other);
+                    let count = &mut self.compatible[idx][direction];
+                    if *count == 0 {
+                        continue;
+                    }
+
+                    *count -= 1;
+                    if *count == 0 {
+                        self.remove(neighbor, other);
+                    }
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, cell: usize, pattern: usize) {
+        let idx = self.idx(cell, pattern);
+        if !self.possible[idx] {
+            return;
+        }
+
+        self.possible[idx] = false;
+        self.compatible[idx] = [0; 4];
+        self.options[cell] -= 1;
+        self.contradiction |= self.options[cell] == 0;
+        self.removed.push((cell, pattern));
+    }
+
+    /// The tile at each position, from the pattern of the cell at or nearest
+    /// to its top left.
+    fn tiles(&self, width: usize, height: usize) -> Vec<TileType> {
+        let size = self.patterns.size;
+
+        (0..height)
+            .flat_map(|row| (0..width).map(move |column| (column, row)))
+            .map(|(column, row)| {
+                let cell_column = column.min(self.columns - 1);
+                let cell_row = row.min(self.rows - 1);
+                let cell = cell_row * self.columns + cell_column;
+                let pattern = (0..self.patterns.len())
+                    .find(|&p| self.possible[self.idx(cell, p)])
+                    .expect("Cell is collapsed");
+
+                self.patterns.tiles[pattern][(row - cell_row) * size + column - cell_column]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::WfcConfig, *};
+
+    #[test]
+    fn windows_match_sample_patterns() {
+        let patterns = sample_patterns();
+        let (width, height) = (24, 16);
+        let mut rng = RandomGenerator::from_seed(7);
+
+        let tiles = (0..10)
+            .find_map(|_| Wave::collapse(&patterns, width, height, &mut rng))
+            .expect("Every attempt contradicted");
+
+        for row in 0..=height - patterns.size {
+            for column in 0..=width - patterns.size {
+                let window = (0..patterns.size * patterns.size)
+                    .map(|i| tiles[(row + i / patterns.size) * width + column + i % patterns.size])
+                    .collect::<Vec<_>>();
+                assert!(
+                    patterns.tiles.contains(&window),
+                    "window at {column},{row} is not in the sample"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_tiles() {
+        let patterns = sample_patterns();
+
+        let first = Wave::collapse(&patterns, 30, 20, &mut RandomGenerator::from_seed(3));
+        let second = Wave::collapse(&patterns, 30, 20, &mut RandomGenerator::from_seed(3));
+
+        assert_eq!(first, second);
+    }
+
+    fn sample_patterns() -> Patterns {
+        let sample = WfcConfig::default().sample;
+
+        Patterns::learn(&sample, 3)
+    }
+}
@@ -0,0 +1,249 @@
+use super::*;
+use crate::{player::PlayerId, prelude::*};
+
+/// Builds levels that look like a small hand-drawn sample map, by learning the
+/// sample's tile patterns and generating the map from them with wave function
+/// collapse. See [`Wave`].
+pub struct WfcBuilder {
+    map: DungeonMap,
+}
+
+impl DungeonBuilder for WfcBuilder {
+    type Config = WfcConfig;
+
+    fn build(
+        config: &WfcConfig,
+        size: MapSize,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> DungeonMap {
+        info!("Building wave function collapse dungeon.");
+
+        let patterns = Patterns::learn(&config.sample, config.pattern_size);
+        let Some(tiles) = Self::collapse(config, &patterns, size, rng) else {
+            error!(
+                "Wave function collapse failed {} times. Building cellular automata dungeon instead.",
+                config.max_attempts
+            );
+            let fallback = CellAutomataConfig {
+                num_items: config.num_items,
+                num_monsters: config.num_monsters,
+                ..default()
+            };
+            return CellAutomataBuilder::build(&fallback, size, num_players, rng);
+        };
+
+        Self {
+            map: DungeonMap::new(size),
+        }
+        .set_tiles(tiles)
+        .set_center()
+        .add_player_starting_positions(num_players, rng)
+        .remove_unreachable_floors()
+        .map
+        .populate(config.num_items, config.num_monsters, rng)
+    }
+}
+
+impl WfcBuilder {
+    /// Each player starts at the floor tile nearest a different corner of the
+    /// dungeon, with the first two players in opposite corners. If the player
+    /// cannot reach the center, we tunnel to it.
+    fn add_player_starting_positions(
+        mut self,
+        num_players: usize,
+        rng: &mut RandomGenerator,
+    ) -> Self {
+        for (player_id, corner) in DungeonCorner::for_players(num_players, rng)
+            .into_iter()
+            .enumerate()
+        {
+            let pos = self
+                .nearest_floor(corner.pos(self.map.size()))
+                .expect("The center is a floor");
+            self.ensure_reachable(player_id, pos);
+            self.map.player_starting_positions.push(pos);
+        }
+
+        self
+    }
+
+    /// Collapse the map, retrying with a new seed on a contradiction. The seeds
+    /// come from the shared generator, so all peers build the same map.
+    fn collapse(
+        config: &WfcConfig,
+        patterns: &Patterns,
+        size: MapSize,
+        rng: &mut RandomGenerator,
+    ) -> Option<Vec<TileType>> {
+        (1..=config.max_attempts).find_map(|attempt| {
+            let seed = rng.gen_range(0..u64::MAX);
+            let mut attempt_rng = RandomGenerator::from_seed(seed);
+            let tiles = Wave::collapse(patterns, size.width, size.height, &mut attempt_rng);
+            if tiles.is_none() {
+                warn!("Wave function collapse contradiction on attempt {attempt} (seed {seed})");
+            }
+
+            tiles
+        })
+    }
+
+    /// Tunnel from the player segment to the dungeon center segment if the
+    /// player cannot reach the center.
+    fn ensure_reachable(&mut self, player_id: PlayerId, pos: DungeonPosition) {
+        let finder = AStarPathFinder::find(pos, self.map.center, &self.map);
+        if finder.path_found() {
+            return;
+        }
+
+        warn!("No path found from player {player_id} to center.");
+        let player_side = finder.closest_position();
+        let center_side =
+            AStarPathFinder::find(self.map.center, player_side, &self.map).closest_position();
+
+        Tunneler::tunnel(&mut self.map, player_side, center_side);
+    }
+
+    /// The floor tile nearest the target. Unlike
+    /// [`DungeonMap::find_nearest_floor_tile`], the search is not limited to a
+    /// radius since the sample may have large walled areas.
+    fn nearest_floor(&self, target: DungeonPosition) -> Option<DungeonPosition> {
+        self.map
+            .tiles()
+            .filter(|t| t.tile_type == TileType::Floor)
+            .map(|t| t.pos)
+            .min_by(|a, b| a.distance(target).total_cmp(&b.distance(target)))
+    }
+
+    /// Wall off floor tiles that cannot be reached from the center, so nothing
+    /// spawns there.
+    fn remove_unreachable_floors(mut self) -> Self {
+        let mut distances = DijkstraMap::new();
+        distances.compute(self.map.center, &self.map);
+
+        self.map
+            .tiles()
+            .filter(|t| t.tile_type == TileType::Floor && distances.distance(&t.pos).is_none())
+            .map(|t| t.pos)
+            .collect::<Vec<_>>()
+            .iter()
+            .for_each(|pos| self.map.set_tile_type(pos, TileType::Wall));
+
+        self
+    }
+
+    /// Reset the map center to the floor tile nearest the absolute center,
+    /// making it a floor if there is none.
+    fn set_center(mut self) -> Self {
+        let center = self
+            .nearest_floor(self.map.center)
+            .unwrap_or(self.map.center);
+        self.map.set_tile_type(&center, TileType::Floor);
+        self.map.center = center;
+
+        self
+    }
+
+    /// Copy the collapsed tiles (top row first) to the map, except for the
+    /// perimeter which is left as walls.
+    fn set_tiles(mut self, tiles: Vec<TileType>) -> Self {
+        let size = self.map.size();
+        tiles
+            .into_iter()
+            .enumerate()
+            .map(|(idx, tile_type)| {
+                let column = (idx % size.width) as isize;
+                let row = (idx / size.width) as isize;
+                let pos = DungeonPosition::new(size.x_min() + column, size.y_max() - row);
+                (pos, tile_type)
+            })
+            .filter(|(pos, _)| !size.at_perimeter(pos))
+            .for_each(|(pos, tile_type)| self.map.set_tile_type(&pos, tile_type));
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn deterministic() {
+        let config = WfcConfig::default();
+
+        let first = WfcBuilder::build(&config, default(), 2, &mut RandomGenerator::from_seed(11));
+        let second = WfcBuilder::build(&config, default(), 2, &mut RandomGenerator::from_seed(11));
+
+        let tile_types = |map: &DungeonMap| map.tiles().map(|t| t.tile_type).collect::<Vec<_>>();
+        assert_eq!(tile_types(&first), tile_types(&second));
+        assert_eq!(
+            first.player_starting_positions,
+            second.player_starting_positions
+        );
+        assert_eq!(first.item_positions, second.item_positions);
+    }
+
+    #[test]
+    fn perimeter_is_wall() {
+        let mut rng = RandomGenerator::new();
+
+        let map = WfcBuilder::build(&WfcConfig::default(), default(), 1, &mut rng);
+
+        assert!(map
+            .tiles()
+            .filter(|t| map.size().at_perimeter(&t.pos))
+            .all(|t| t.tile_type == TileType::Wall));
+    }
+
+    #[test]
+    fn exit_at_dungeon_center() {
+        let mut rng = RandomGenerator::new();
+
+        let map = WfcBuilder::build(&WfcConfig::default(), default(), 1, &mut rng);
+
+        assert_eq!(map.get_tile_type(&map.center), TileType::Exit);
+    }
+
+    #[rstest]
+    #[case::one_player(1)]
+    #[case::four_players(4)]
+    fn player_positions(#[case] num_players: usize) {
+        let mut rng = RandomGenerator::new();
+
+        let map = WfcBuilder::build(&WfcConfig::default(), default(), num_players, &mut rng);
+
+        assert_eq!(map.player_starting_positions.len(), num_players);
+        for player_pos in &map.player_starting_positions {
+            assert!(AStarPathFinder::find(*player_pos, map.center, &map).path_found());
+        }
+    }
+
+    #[test]
+    fn all_floors_reachable() {
+        let mut rng = RandomGenerator::new();
+
+        let map = WfcBuilder::build(&WfcConfig::default(), default(), 1, &mut rng);
+
+        let mut distances = DijkstraMap::new();
+        distances.compute(map.center, &map);
+        let unreachable = map
+            .tiles()
+            .filter(|t| t.tile_type == TileType::Floor)
+            .find(|t| distances.distance(&t.pos).is_none())
+            .map(|t| t.pos);
+        assert!(unreachable.is_none(), "unreachable floor {unreachable:?}");
+    }
+
+    #[test]
+    fn dungeon_has_items_and_monsters() {
+        let config = WfcConfig::default();
+        let mut rng = RandomGenerator::new();
+
+        let map = WfcBuilder::build(&config, default(), 1, &mut rng);
+
+        assert_eq!(map.item_positions.len(), config.num_items);
+        assert_eq!(map.monster_starting_positions.len(), config.num_monsters);
+    }
+}
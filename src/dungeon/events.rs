@@ -7,11 +7,26 @@ pub struct DungeonEventsPlugin;
 
 impl Plugin for DungeonEventsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<RevealDungeonEvent>()
+        app.add_event::<NextLevelEvent>()
+            .add_event::<RevealDungeonEvent>()
             .add_event::<ZoomEvent>();
     }
 }
 
+/// A player took the stairs at the exit, so we need to descend to the next
+/// dungeon level.
+#[derive(Event)]
+pub struct NextLevelEvent {
+    /// The level being descended to
+    pub level: usize,
+}
+
+impl NextLevelEvent {
+    pub fn new(level: usize) -> Self {
+        Self { level }
+    }
+}
+
 /// Player used the magic dungeon map item or used the reveal map cheat
 #[derive(Event)]
 pub struct RevealDungeonEvent {
@@ -1,50 +1,73 @@
-use super::{FloorTile, FLOOR_COLOR, FLOOR_ILLUMINATED_COLOR};
-use crate::{fov::FovTileMap, prelude::*};
+use super::{ExitTile, FloorTile, WallTile, EXIT_COLOR, EXIT_ILLUMINATED_COLOR};
+use crate::{common::DungeonTheme, fov::FovTileMap, prelude::*};
 use bevy::utils::hashbrown::HashSet;
 
-pub type FloorQuery<'w, 's, 't, 'r, 'v> =
-    Query<'w, 's, (&'t Transform, Entity, &'r mut Sprite, &'v mut Visibility), With<FloorTile>>;
+pub type TileQuery<'w, 's, 't, 'r, 'v> = Query<
+    'w,
+    's,
+    (
+        &'t Transform,
+        Entity,
+        Has<ExitTile>,
+        Has<WallTile>,
+        &'r mut Sprite,
+        &'v mut Visibility,
+    ),
+    Or<(With<FloorTile>, With<WallTile>)>,
+>;
 pub type PlayerQuery<'w, 's, 'p> = Query<'w, 's, &'p Player>;
 
-/// Illuminate or darken floor tiles based on the local player's FOV.
-pub struct Illuminator {
+/// Illuminate or darken floor tiles (including the exit) and the walls bordering
+/// them based on the local player's FOV, using the colors of the level's theme.
+pub struct Illuminator<'a> {
     prior_set: HashSet<Entity>,
+    theme: &'a DungeonTheme,
 }
 
-impl Illuminator {
+impl<'a> Illuminator<'a> {
     /// This is only relevant for the local player. The remaining methods ignore
     /// the case where the entity is not the local player.
-    pub fn new(prior_fov: &FovTileMap) -> Self {
+    pub fn new(prior_fov: &FovTileMap, theme: &'a DungeonTheme) -> Self {
         Self {
             prior_set: prior_fov.values().map(|e| *e).collect(),
+            theme,
         }
     }
 
-    /// Illuminate the floor tiles that are in the local player's FOV
+    /// Illuminate the tiles that are in the local player's FOV
     /// and darken those no longer in FOV (but leave them visible).
-    pub fn illuminate(mut self, floor: &mut FloorQuery, fov: &FovTileMap) {
+    pub fn illuminate(mut self, tiles: &mut TileQuery, fov: &FovTileMap) {
         fov.values().for_each(|tile| {
             if self.prior_set.contains(tile) {
                 // already illuminated, so remove it from the prior set
                 self.prior_set.remove(tile);
             } else {
-                let (.., mut sprite, mut visibility) =
-                    floor.get_mut(*tile).expect("Inconceivable!");
-                sprite.color = FLOOR_ILLUMINATED_COLOR;
+                let (_, _, is_exit, is_wall, mut sprite, mut visibility) =
+                    tiles.get_mut(*tile).expect("Inconceivable!");
+                sprite.color = match (is_exit, is_wall) {
+                    (true, _) => EXIT_ILLUMINATED_COLOR,
+                    (false, true) => self.theme.wall.illuminated(),
+                    (false, false) => self.theme.floor.illuminated(),
+                };
                 *visibility = Visibility::Visible;
             }
         });
 
-        self.darken_discarded_prior(floor);
+        self.darken_discarded_prior(tiles);
     }
 
     /// darken tiles that were previously illuminated and no longer in FOV
     /// At this point the prior FOV set should only contain tiles that should
     /// be darkened.
-    fn darken_discarded_prior(&self, floor: &mut FloorQuery) {
+    fn darken_discarded_prior(&self, tiles: &mut TileQuery) {
         self.prior_set.iter().for_each(|tile| {
-            let (.., mut sprite, _) = floor.get_mut(*tile).expect("Inconceivable!");
-            sprite.color = FLOOR_COLOR;
+            let (_, _, is_exit, is_wall, mut sprite, _) =
+                tiles.get_mut(*tile).expect("Inconceivable!");
+            sprite.color = match (is_exit, is_wall) {
+                (true, _) => EXIT_COLOR,
+                (false, true) => self.theme.wall.color(),
+                (false, false) => self.theme.floor.color(),
+            };
         });
     }
 }
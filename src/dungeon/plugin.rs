@@ -1,5 +1,15 @@
-use super::{reveal_map, spawn_dungeon, zoom, DungeonEventsPlugin};
-use crate::{common, prelude::*};
+use super::{
+    descend, despawn_dungeon, reveal_map, spawn_dungeon, take_stairs, update_dijkstra_maps, zoom,
+    DijkstraMap, DungeonEventsPlugin, NextLevelEvent,
+};
+use crate::{
+    common,
+    game_states::Pause,
+    monsters::MonstersCoreSet,
+    player::{PlayerCoreSet, SpawnPlayersSet},
+    prelude::*,
+};
+use bevy_ggrs::GgrsApp;
 
 #[derive(SystemSet, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct SpawnDungeonSet;
@@ -17,5 +27,40 @@ impl Plugin for DungeonPlugin {
         );
 
         common::add_core_systems(app, (reveal_map, zoom).in_set(DungeonCoreSet));
+
+        // Descending to the next level replaces the dungeon in the GgrsSchedule
+        // so that all GGRS peers do so on the same frame. The core systems are
+        // frozen until then, so descend runs outside them.
+        common::add_game_systems(
+            app,
+            descend
+                .run_if(in_state(GameState::InGame).and(Pause::running))
+                .after(PlayerCoreSet)
+                .before(take_stairs),
+        );
+
+        common::add_core_systems(
+            app,
+            (
+                take_stairs,
+                (despawn_dungeon, spawn_dungeon)
+                    .chain()
+                    .run_if(on_event::<NextLevelEvent>)
+                    .in_set(SpawnDungeonSet),
+            )
+                .chain()
+                .after(PlayerCoreSet)
+                .before(MonstersCoreSet),
+        );
+
+        common::add_core_systems(
+            app,
+            update_dijkstra_maps
+                .after(PlayerCoreSet)
+                .after(SpawnPlayersSet)
+                .before(MonstersCoreSet),
+        );
+
+        app.rollback_component_with_clone::<DijkstraMap>();
     }
 }
@@ -1,13 +1,15 @@
-use super::{FloorTile, RevealDungeonEvent};
+use super::{FloorTile, RevealDungeonEvent, WallKind};
 use crate::player::LocalPlayer;
 use bevy::prelude::*;
 use bevy_ggrs::LocalPlayers;
 
-/// Reveal the map to player who used the magic map item or to both players if
-/// one of them used the reveal map cheat
+/// Reveal the map to player who used the magic map item or to all players if
+/// one of them used the reveal map cheat. Interior walls stay hidden since they
+/// never border a floor tile.
+#[allow(clippy::type_complexity)]
 pub fn reveal_map(
     mut reveal_events: EventReader<RevealDungeonEvent>,
-    mut tiles: Query<&mut Visibility, With<FloorTile>>,
+    mut tiles: Query<(&mut Visibility, Option<&WallKind>), Or<(With<FloorTile>, With<WallKind>)>>,
     local_players: Res<LocalPlayers>,
 ) {
     let Some(event) = reveal_events.read().next() else {
@@ -22,8 +24,10 @@ pub fn reveal_map(
 
     tiles
         .iter_mut()
-        .filter(|v| **v == Visibility::Hidden)
-        .for_each(|mut v| {
+        .filter(|(v, wall_kind)| {
+            **v == Visibility::Hidden && wall_kind != &Some(&WallKind::Interior)
+        })
+        .for_each(|(mut v, _)| {
             *v = Visibility::Visible;
         });
 
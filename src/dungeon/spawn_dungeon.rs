@@ -1,40 +1,106 @@
 use super::*;
-use crate::{player::Obstacle, prelude::*};
+use crate::{
+    common::{DungeonAssets, DungeonTheme, SpriteAssets, ThemeData},
+    player::Obstacle,
+    prelude::*,
+};
 
-pub fn spawn_dungeon(mut commands: Commands, mut rng: ResMut<RandomGenerator>) {
-    let dungeon = match rng.gen_range(0..3) {
-        0 => CellAutomataBuilder::build(rng.as_mut()),
-        1 => DrunkardsWalkBuilder::build(DrunkardsWalkConfig::default(), rng.as_mut()),
-        2 => RandomRoomsBuilder::build(rng.as_mut()),
-        _ => unreachable!(),
-    };
+/// Build a new dungeon level and spawn its tiles. Runs when entering the game
+/// (level 1) and whenever a player takes the stairs to the next level.
+/// The builder is picked at random per the weights in `builders.ron`.
+/// Each level also picks a random [`DungeonTheme`], which is inserted as a
+/// resource for the systems that spawn monsters and items or illuminate tiles.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_dungeon(
+    builder_data_assets: Res<Assets<BuilderData>>,
+    dungeon_assets: Res<DungeonAssets>,
+    mut commands: Commands,
+    mut next_level_events: EventReader<NextLevelEvent>,
+    num_players: Res<NumPlayers>,
+    prefab_data_assets: Res<Assets<PrefabData>>,
+    mut rng: ResMut<RandomGenerator>,
+    sprite_assets: Res<SpriteAssets>,
+    theme_data_assets: Res<Assets<ThemeData>>,
+) {
+    let level = next_level_events.read().last().map_or(1, |e| e.level);
+
+    let dungeon = build_dungeon(
+        builder_data_assets
+            .get(&dungeon_assets.builders)
+            .expect("Failed to load builder data"),
+        prefab_data_assets
+            .get(&dungeon_assets.prefabs)
+            .expect("Failed to load prefab data"),
+        num_players.0,
+        level,
+        rng.as_mut(),
+    );
+    let theme = pick_theme(theme_data_assets.get(&dungeon_assets.themes), rng.as_mut());
+    info!("Spawning dungeon level {level} with theme {}", theme.name);
 
     for tile in dungeon.tiles() {
-        let sprite = create_sprite(tile.tile_type);
         let transform = Transform::from_translation(tile.pos.into());
 
-        let mut tile_entity = match tile.tile_type {
-            TileType::Exit => commands.spawn((ExitTile,)),
-            TileType::Floor => commands.spawn((FloorTile,)),
-            TileType::Wall => commands.spawn((WallTile, Obstacle::Wall)),
+        let (mut tile_entity, sprite) = match tile.tile_type {
+            TileType::Exit => (
+                commands.spawn((ExitTile, FloorTile)),
+                sprite_assets.sprite(Some(EXIT_SPRITE), EXIT_COLOR),
+            ),
+            TileType::Floor => (
+                commands.spawn((FloorTile,)),
+                sprite_assets.sprite(Some(FLOOR_SPRITE), theme.floor.color()),
+            ),
+            TileType::Wall => {
+                let wall_kind = WallKind::new(&tile.pos, &dungeon);
+                (
+                    commands.spawn((WallTile, wall_kind, Obstacle::Wall)),
+                    sprite_assets.sprite(Some(wall_sprite(wall_kind)), theme.wall.color()),
+                )
+            }
         };
 
-        tile_entity.insert((sprite, transform, Visibility::Hidden));
+        tile_entity.insert((sprite, transform, InGameEntity, Visibility::Hidden));
     }
 
+    commands.insert_resource(ClearColor(theme.ambient()));
     commands.insert_resource(dungeon);
+    commands.insert_resource(theme);
+}
+
+/// Build the [`DungeonMap`] of the given level with a builder picked at random
+/// and stamp a prefab vault into it. Also used by the `dungeon_gen` tool.
+pub fn build_dungeon(
+    builder_data: &BuilderData,
+    prefab_data: &PrefabData,
+    num_players: usize,
+    level: usize,
+    rng: &mut RandomGenerator,
+) -> DungeonMap {
+    let mut dungeon = builder_data.pick(rng).build(num_players, rng);
+    dungeon.level = level;
+    if let Some(amulet_pos) = dungeon.amulet_position() {
+        // no way further down from the deepest level
+        dungeon.set_tile_type(&amulet_pos, TileType::Floor);
+    }
+    VaultStamper::stamp(&mut dungeon, &prefab_data.prefabs, rng);
+
+    dungeon
+}
+
+fn pick_theme(theme_data: Option<&ThemeData>, rng: &mut RandomGenerator) -> DungeonTheme {
+    let themes = &theme_data.expect("Failed to load theme data").themes;
+    assert!(!themes.is_empty(), "No dungeon themes defined");
+
+    themes[rng.gen_range(0..themes.len())].clone()
 }
 
-fn create_sprite(tile_type: TileType) -> Sprite {
-    let color = match tile_type {
-        TileType::Exit => Color::srgb(1., 1., 1.),
-        TileType::Floor => FLOOR_COLOR,
-        TileType::Wall => Color::srgb(0., 0., 0.),
-    };
-
-    Sprite {
-        color,
-        custom_size: Some(Vec2::new(TILE_HEIGHT, TILE_WIDTH)),
-        ..default()
+/// Pick the atlas sprite for the wall's autotile variant.
+fn wall_sprite(wall_kind: WallKind) -> usize {
+    match wall_kind {
+        WallKind::Corner => WALL_CORNER_SPRITE,
+        WallKind::Horizontal => WALL_HORIZONTAL_SPRITE,
+        WallKind::Interior => WALL_SPRITE,
+        WallKind::Pillar => WALL_PILLAR_SPRITE,
+        WallKind::Vertical => WALL_VERTICAL_SPRITE,
     }
 }
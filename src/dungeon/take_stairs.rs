@@ -0,0 +1,33 @@
+use super::{DungeonPosition, TileType};
+use crate::{
+    common::{Change, PendingChange},
+    player::PlayerMovesEvent,
+    prelude::*,
+};
+
+/// Request the descent to the next dungeon level when a player steps onto the
+/// exit. The move may still be rolled back, so [`super::descend`] only replaces
+/// the level once the request is due.
+pub fn take_stairs(
+    dungeon: Res<DungeonMap>,
+    mut move_events: EventReader<PlayerMovesEvent>,
+    mut pending: ResMut<PendingChange>,
+) {
+    let Some(event) = move_events
+        .read()
+        .filter(|e| {
+            dungeon.get_tile_type(&DungeonPosition::from_vec2(e.pos.as_vec2())) == TileType::Exit
+        })
+        .last()
+    else {
+        return;
+    };
+
+    let level = dungeon.level + 1;
+    info!(
+        "Player {} takes the stairs to level {level}",
+        event.player_id
+    );
+
+    pending.request(Change::NextLevel(level));
+}
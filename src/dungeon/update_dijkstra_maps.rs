@@ -0,0 +1,14 @@
+use super::{DijkstraMap, DungeonPosition};
+use crate::prelude::*;
+
+/// Recompute the [`DijkstraMap`] leading to each player that moved (or was
+/// spawned or placed on a new level) so monsters can chase them around walls.
+#[allow(clippy::type_complexity)]
+pub fn update_dijkstra_maps(
+    dungeon: Res<DungeonMap>,
+    mut players: Query<(&mut DijkstraMap, &Transform), (With<Player>, Changed<Transform>)>,
+) {
+    for (mut dijkstra_map, transform) in &mut players {
+        dijkstra_map.compute(DungeonPosition::from_vec3(transform.translation), &dungeon);
+    }
+}
@@ -1,16 +1,37 @@
+mod end_game;
 mod game_over;
+mod game_won;
+mod pause;
 mod plugin;
+mod restart_game;
+mod tear_down_game;
 
+pub use game_over::GameOverReport;
+pub use game_won::Winner;
+pub use pause::Pause;
 pub use plugin::GameStatesPlugin;
+pub use tear_down_game::{tear_down_game, GameEntityQuery};
 
 use bevy::prelude::*;
 
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, States)]
 pub enum GameState {
     GameOver,
+    GameWon,
     InGame,
     #[default]
     Loading,
+    /// Host or join a multiplayer room (unless given as a launch option)
+    Lobby,
+    /// Choose the game mode (unless given as a launch option)
+    MainMenu,
+    /// Frozen after a desync (see the desync report). The pause menu does not
+    /// leave `InGame`, see [`Pause`].
     Paused,
     Startup,
 }
+
+/// Marks entities that belong to the game in progress (tiles, monsters, items,
+/// players, HUD nodes etc.) so they can be torn down when restarting.
+#[derive(Component)]
+pub struct InGameEntity;
@@ -0,0 +1,36 @@
+use super::Winner;
+use crate::{
+    common::{Change, PendingChange},
+    prelude::*,
+};
+
+/// End the game once the requested game over or win is due (see
+/// [`PendingChange`]), so all peers leave [`GameState::InGame`] after the same
+/// frame. The core systems stay frozen from then on since the
+/// [`GameOverReport`](super::GameOverReport) or [`Winner`] is known, even for
+/// frames GGRS advances before the state changes.
+pub fn end_game(
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut pending: ResMut<PendingChange>,
+) {
+    let Some(change @ (Change::GameOver(_) | Change::Win(_))) = pending.change() else {
+        return;
+    };
+
+    if !pending.tick() {
+        return;
+    }
+
+    match change {
+        Change::GameOver(report) => {
+            commands.insert_resource(report);
+            next_state.set(GameState::GameOver);
+        }
+        Change::Win(player_id) => {
+            commands.insert_resource(Winner::new(player_id));
+            next_state.set(GameState::GameWon);
+        }
+        Change::NextLevel(_) | Change::Restart => unreachable!(),
+    }
+}
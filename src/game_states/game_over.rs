@@ -1,5 +1,26 @@
-use bevy::log::info;
+use crate::{monsters::Monster, player::PlayerId, prelude::*};
 
-pub fn game_over() {
-    info!("GAME OVER");
+/// Details of the player death that ended the game, shown on the game over screen.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Resource)]
+pub struct GameOverReport {
+    /// The monster that killed the player
+    pub cause: Monster,
+    pub monsters_killed: usize,
+    pub player_id: PlayerId,
+    pub turns: usize,
+}
+
+pub fn game_over(report: Res<GameOverReport>) {
+    let GameOverReport {
+        cause,
+        monsters_killed,
+        player_id,
+        turns,
+    } = report.as_ref();
+
+    info!(
+        "GAME OVER: Player {player_id} killed by {} after {turns} turns \
+        and {monsters_killed} monsters killed",
+        cause.name()
+    );
 }
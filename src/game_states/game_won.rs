@@ -0,0 +1,17 @@
+use crate::{player::PlayerId, prelude::*};
+
+/// The player that found the Amulet of Yala and thereby won the game.
+#[derive(Resource)]
+pub struct Winner {
+    pub player_id: PlayerId,
+}
+
+impl Winner {
+    pub fn new(player_id: PlayerId) -> Self {
+        Self { player_id }
+    }
+}
+
+pub fn game_won(winner: Res<Winner>) {
+    info!("GAME WON by player {}", winner.player_id);
+}
@@ -0,0 +1,56 @@
+use crate::{
+    player::{PlayerAction, PlayerId},
+    prelude::*,
+};
+use bevy_ggrs::PlayerInputs;
+
+/// Whether the game is paused, which freezes the core systems. It only changes
+/// on player inputs and is rolled back by GGRS, so all peers pause (and resume)
+/// on the same frame.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Resource)]
+pub struct Pause {
+    /// Player that paused the game, if paused
+    pub paused_by: Option<PlayerId>,
+}
+
+impl Pause {
+    pub fn is_paused(&self) -> bool {
+        self.paused_by.is_some()
+    }
+
+    /// Run condition for systems that freeze while the game is paused.
+    pub fn running(pause: Res<Pause>) -> bool {
+        !pause.is_paused()
+    }
+}
+
+/// Pause or resume the game when any player presses Escape. In GGRS modes, the
+/// key press is shared as a [`PlayerAction`] input so all peers toggle on the
+/// same frame.
+pub fn toggle_pause(
+    ggrs_inputs: Option<Res<PlayerInputs<config::GgrsSessionConfig>>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut pause: ResMut<Pause>,
+) {
+    let requested_by = if let Some(ggrs_inputs) = ggrs_inputs {
+        ggrs_inputs
+            .iter()
+            .position(|(input, _)| PlayerAction::from(*input) == PlayerAction::Pause)
+    } else {
+        keys.just_pressed(KeyCode::Escape).then_some(0)
+    };
+
+    let Some(player_id) = requested_by else {
+        return;
+    };
+
+    pause.paused_by = match pause.paused_by {
+        Some(_) => None,
+        None => Some(player_id),
+    };
+
+    match pause.paused_by {
+        Some(_) => info!("Game paused by player {player_id}"),
+        None => info!("Game resumed by player {player_id}"),
+    }
+}
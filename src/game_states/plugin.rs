@@ -1,11 +1,55 @@
-use super::game_over::game_over;
-use crate::prelude::*;
+use super::{
+    end_game::end_game,
+    game_over::{game_over, GameOverReport},
+    game_won::{game_won, Winner},
+    pause::{toggle_pause, Pause},
+    restart_game::{request_restart, restart_game},
+};
+use crate::{
+    common::{self, PendingChange},
+    monsters::MonstersCoreSet,
+    player::PlayerCoreSet,
+    prelude::*,
+};
+use bevy_ggrs::GgrsApp;
 
 pub struct GameStatesPlugin;
 
 impl Plugin for GameStatesPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
-            .add_systems(OnEnter(GameState::GameOver), game_over);
+            .init_resource::<Pause>()
+            .add_systems(OnEnter(GameState::GameOver), game_over)
+            .add_systems(OnEnter(GameState::GameWon), game_won);
+
+        // Ordered after the InGame systems only to settle ambiguities, since
+        // they never run in the same frame. A pending restart goes ahead even
+        // if the game is resumed in the meantime. Ending and restarting the
+        // game do not depend on the end screen states, since GGRS may
+        // re-simulate frames from before the state changed after it did.
+        common::add_game_systems(
+            app,
+            (
+                end_game.run_if(Pause::running),
+                request_restart.run_if(
+                    resource_exists::<GameOverReport>
+                        .or(resource_exists::<Winner>)
+                        .or(in_state(GameState::InGame).and(not(Pause::running))),
+                ),
+                restart_game.run_if(PendingChange::restart),
+            )
+                .chain()
+                .after(MonstersCoreSet),
+        );
+
+        common::add_game_systems(
+            app,
+            toggle_pause
+                .run_if(in_state(GameState::InGame))
+                .before(PlayerCoreSet),
+        );
+
+        app.rollback_resource_with_copy::<Pause>()
+            .checksum_resource_with_hash::<Pause>();
     }
 }
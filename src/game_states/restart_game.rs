@@ -0,0 +1,64 @@
+use super::{tear_down_game, GameEntityQuery};
+use crate::{
+    common::{Change, PendingChange},
+    player::PlayerAction,
+    prelude::*,
+};
+use bevy_ggrs::PlayerInputs;
+
+/// Request a new game when any player presses the restart key once the game has
+/// ended or from the pause menu. In GGRS modes, the key press is shared as a
+/// [`PlayerAction`] input, which may be mispredicted, so [`restart_game`] only
+/// restarts once the request is due (see [`PendingChange`]). Holding the key
+/// does not postpone it.
+pub fn request_restart(
+    ggrs_inputs: Option<Res<PlayerInputs<config::GgrsSessionConfig>>>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut pending: ResMut<PendingChange>,
+) {
+    if pending.change() == Some(Change::Restart) {
+        return;
+    }
+
+    let restart = if let Some(ggrs_inputs) = ggrs_inputs {
+        ggrs_inputs
+            .iter()
+            .any(|(input, _)| PlayerAction::from(*input) == PlayerAction::Restart)
+    } else {
+        PlayerAction::from(keys.as_mut()) == PlayerAction::Restart
+    };
+
+    if restart {
+        info!("Restart requested");
+        pending.request(Change::Restart);
+    }
+}
+
+/// Start a new game once the requested restart is due, on the same frame for
+/// all peers. The game is torn down (see [`tear_down_game`]) and the random
+/// generator is reseeded (identically on all peers) before re-entering
+/// [`GameState::InGame`], which spawns a brand new game.
+pub fn restart_game(
+    entities: GameEntityQuery,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut pending: ResMut<PendingChange>,
+    mut rng: ResMut<RandomGenerator>,
+    state: Res<State<GameState>>,
+) {
+    if !pending.tick() {
+        return;
+    }
+
+    tear_down_game(&mut commands, &entities);
+    rng.reseed();
+    info!("Restarting game with random seed {}", rng.seed);
+
+    if *state.get() == GameState::InGame {
+        // Re-entering the current state does not run OnEnter, so spawn the new
+        // game directly once the old one is torn down.
+        commands.queue(|world: &mut World| world.run_schedule(OnEnter(GameState::InGame)));
+    } else {
+        next_state.set(GameState::InGame);
+    }
+}
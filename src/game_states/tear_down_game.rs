@@ -0,0 +1,26 @@
+use super::{GameOverReport, Pause, Winner};
+use crate::{
+    common::{DungeonTheme, PendingChange},
+    prelude::*,
+};
+use bevy_ggrs::Rollback;
+
+/// All [`InGameEntity`] entities, along with the rollback entities GGRS may
+/// have respawned without it
+pub type GameEntityQuery<'w, 's> = Query<'w, 's, Entity, Or<(With<InGameEntity>, With<Rollback>)>>;
+
+/// Despawn the game in progress and reset what it leaves behind (the level, the
+/// end of game resources, the pause menu and any pending change), so the next
+/// game starts afresh whether it is a restart or a new session.
+pub fn tear_down_game(commands: &mut Commands, entities: &GameEntityQuery) {
+    entities
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+    commands.remove_resource::<DungeonMap>();
+    commands.remove_resource::<DungeonTheme>();
+    commands.remove_resource::<GameOverReport>();
+    commands.remove_resource::<Winner>();
+    commands.insert_resource(ClearColor::default());
+    commands.insert_resource(Pause::default());
+    commands.insert_resource(PendingChange::default());
+}
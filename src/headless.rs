@@ -0,0 +1,9 @@
+mod component_checksums;
+mod headless_app;
+mod loopback_socket;
+mod two_peer_harness;
+
+pub use component_checksums::ComponentChecksums;
+pub use headless_app::HeadlessApp;
+pub use loopback_socket::{LoopbackNetwork, LoopbackSocket, NetworkConditions};
+pub use two_peer_harness::{random_script, PeerDesync, TwoPeerHarness};
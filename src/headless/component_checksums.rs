@@ -0,0 +1,81 @@
+use crate::{
+    game_states::Pause,
+    items::{Grabbable, MagicItem, Weapon},
+    monsters::{Behavior, Monster, TargetMemory},
+    player::{Inventory, MoveThrottle, Obstacle, PlayerStats},
+    prelude::*,
+    startup::checksum_transform,
+};
+use bevy_ggrs::{checksum_hasher, Rollback, RollbackFrameCount};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+};
+
+/// Checksum of each rollback component (and the random generator) after each
+/// frame, so peers can be compared component by component. Frames re-simulated
+/// on rollback overwrite the earlier checksums.
+#[derive(Debug, Default, Resource)]
+pub struct ComponentChecksums(pub BTreeMap<i32, Vec<(&'static str, u64)>>);
+
+/// Record the checksums of the frame just advanced. Covers the same components
+/// and resources the plugins register for GGRS checksums.
+pub fn record_component_checksums(world: &mut World) {
+    let frame = world.resource::<RollbackFrameCount>().0;
+    let checksums = vec![
+        resource_checksum::<Pause>(world),
+        resource_checksum::<RandomGenerator>(world),
+        ("Transform", sum(world, checksum_transform)),
+        checksum::<Behavior>(world),
+        checksum::<Damage>(world),
+        checksum::<Grabbable>(world),
+        checksum::<Health>(world),
+        checksum::<Inventory>(world),
+        checksum::<MagicItem>(world),
+        checksum::<Monster>(world),
+        checksum::<MoveThrottle>(world),
+        checksum::<Obstacle>(world),
+        checksum::<Player>(world),
+        checksum::<PlayerStats>(world),
+        checksum::<TargetMemory>(world),
+        checksum::<Weapon>(world),
+    ];
+
+    world
+        .resource_mut::<ComponentChecksums>()
+        .0
+        .insert(frame, checksums);
+}
+
+fn checksum<C: Component + Hash>(world: &mut World) -> (&'static str, u64) {
+    let checksum = sum(world, |component: &C| {
+        let mut hasher = checksum_hasher();
+        component.hash(&mut hasher);
+        hasher.finish()
+    });
+
+    (name::<C>(), checksum)
+}
+
+fn name<T>() -> &'static str {
+    std::any::type_name::<T>()
+        .rsplit("::")
+        .next()
+        .unwrap_or("(Unknown)")
+}
+
+fn resource_checksum<R: Resource + Hash>(world: &World) -> (&'static str, u64) {
+    let mut hasher = checksum_hasher();
+    world.resource::<R>().hash(&mut hasher);
+
+    (name::<R>(), hasher.finish())
+}
+
+/// Add up the checksums of all rollback entities with the component, so the
+/// result does not depend on query order.
+fn sum<C: Component>(world: &mut World, checksum: impl Fn(&C) -> u64) -> u64 {
+    world
+        .query_filtered::<&C, With<Rollback>>()
+        .iter(world)
+        .fold(0, |acc, component| acc.wrapping_add(checksum(component)))
+}
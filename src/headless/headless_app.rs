@@ -0,0 +1,209 @@
+use crate::{
+    common::{DungeonAssets, DungeonData, SpriteAssets, ThemeData},
+    dungeon::{BuilderData, PrefabData},
+    player::{PlayerAction, PlayerId},
+    prelude::*,
+    replay::{Replay, ReplayPlayer},
+    startup::LaunchOptions,
+    GamePlugins,
+};
+use bevy::{
+    app::ScheduleRunnerPlugin, asset::Asset, input::InputPlugin, scene::ron,
+    state::app::StatesPlugin, time::TimeUpdateStrategy,
+};
+use bevy_ggrs::{ggrs::P2PSession, RollbackFrameCount, Session};
+use serde::de::DeserializeOwned;
+use std::{fs, path::Path, time::Duration};
+
+/// Just under one GGRS frame (at the default 60 fps), so each update advances
+/// the session by at most one frame.
+const UPDATE_DURATION: Duration = Duration::from_micros(16_600);
+
+/// Updates allowed per frame before giving up on the session advancing
+const MAX_UPDATES_PER_FRAME: usize = 10;
+
+/// The game without a window, rendering, HUD or asset loading, stepped one
+/// frame at a time with scripted [`PlayerAction`]s for gameplay tests.
+///
+/// The dungeon data is read straight from the `assets` folder and the scripted
+/// actions are fed to a sync test session the way a [`Replay`] is played back,
+/// so the same GgrsSchedule systems run as in a multiplayer game. Peers of a
+/// P2P session are created by the [`TwoPeerHarness`](super::TwoPeerHarness).
+pub struct HeadlessApp {
+    app: App,
+}
+
+impl HeadlessApp {
+    /// Start a game for the given number of players, running until the level
+    /// has spawned.
+    pub fn new(num_players: usize, seed: u64) -> Self {
+        let mut app = build_app(
+            GameMode::Replay,
+            ReplayPlayer::new(Replay::new(seed, num_players), false),
+        );
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Startup);
+
+        Self::start(app)
+    }
+
+    /// Start a game as one peer of a P2P session, skipping the matchbox
+    /// connection. The scripted inputs hold those of all players, but only the
+    /// local player's are fed to the session.
+    pub fn new_peer(session: P2PSession<config::GgrsSessionConfig>, script: Replay) -> Self {
+        let mut app = build_app(
+            GameMode::MultiPlayer,
+            ReplayPlayer::new(script.clone(), false),
+        );
+        app.insert_resource(RandomGenerator::from_seed(script.seed))
+            .insert_resource(Session::P2P(session));
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+
+        Self::start(app)
+    }
+
+    fn start(app: App) -> Self {
+        let mut headless = Self { app };
+        while headless.state() != GameState::InGame {
+            headless.update();
+        }
+
+        headless
+    }
+
+    /// Advance one frame with the given action for each player.
+    pub fn step(&mut self, actions: &[PlayerAction]) {
+        assert_eq!(
+            actions.len(),
+            self.world().resource::<NumPlayers>().0,
+            "Expected one action per player"
+        );
+
+        let frame = self.frame();
+        let inputs = &mut self
+            .app
+            .world_mut()
+            .resource_mut::<ReplayPlayer>()
+            .replay
+            .inputs;
+        inputs.truncate(frame as usize);
+        inputs.push(actions.iter().map(|&action| action.into()).collect());
+
+        for _ in 0..MAX_UPDATES_PER_FRAME {
+            self.app.update();
+            if self.frame() > frame {
+                return;
+            }
+        }
+
+        panic!("Frame {frame} did not advance");
+    }
+
+    /// Advance the given number of frames with every player idle.
+    pub fn idle(&mut self, frames: usize) {
+        let actions = vec![PlayerAction::None; self.world().resource::<NumPlayers>().0];
+        (0..frames).for_each(|_| self.step(&actions));
+    }
+
+    /// The number of frames advanced by the GGRS session so far
+    pub fn frame(&self) -> i32 {
+        self.world().resource::<RollbackFrameCount>().0
+    }
+
+    /// The entity of the given player, if still in the game.
+    pub fn player(&mut self, id: PlayerId) -> Option<Entity> {
+        self.app
+            .world_mut()
+            .query::<(Entity, &Player)>()
+            .iter(self.app.world())
+            .find(|(_, player)| player.id == id)
+            .map(|(entity, _)| entity)
+    }
+
+    pub fn state(&self) -> GameState {
+        *self.world().resource::<State<GameState>>().get()
+    }
+
+    /// The underlying app, e.g. for a test to add systems of its own
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    /// Run an update outside of a step, feeding idle inputs to any frame the
+    /// session advances beyond the script.
+    pub fn update(&mut self) {
+        let frame = self.frame() as usize;
+        let num_players = self.world().resource::<NumPlayers>().0;
+        let mut replay_player = self.app.world_mut().resource_mut::<ReplayPlayer>();
+        if replay_player.replay.inputs.len() <= frame {
+            replay_player.replay.inputs.push(vec![0; num_players]);
+        }
+
+        self.app.update();
+    }
+}
+
+fn build_app(game_mode: GameMode, replay_player: ReplayPlayer) -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_once()),
+        HierarchyPlugin,
+        InputPlugin,
+        StatesPlugin,
+        TransformPlugin,
+        GamePlugins,
+    ))
+    .insert_resource(game_mode)
+    .insert_resource(LaunchOptions::default())
+    .insert_resource(NumPlayers(replay_player.replay.num_players))
+    .insert_resource(replay_player)
+    .insert_resource(TimeUpdateStrategy::ManualDuration(UPDATE_DURATION));
+    insert_assets(&mut app);
+
+    app
+}
+
+/// Read the dungeon data straight from the asset files in place of the
+/// loading state. Sprites are not rendered, so default handles do.
+fn insert_assets(app: &mut App) {
+    let mut builder_data = Assets::<BuilderData>::default();
+    let mut dungeon_data = Assets::<DungeonData>::default();
+    let mut prefab_data = Assets::<PrefabData>::default();
+    let mut theme_data = Assets::<ThemeData>::default();
+
+    app.insert_resource(DungeonAssets {
+        builders: builder_data.add(read_asset::<BuilderData>("builders.ron")),
+        data: dungeon_data.add(read_asset::<DungeonData>("dungeon.ron")),
+        prefabs: prefab_data.add(read_asset::<PrefabData>("prefabs.ron")),
+        themes: theme_data.add(read_asset::<ThemeData>("themes.ron")),
+    })
+    .insert_resource(SpriteAssets {
+        layout: Handle::default(),
+        sheet: Handle::default(),
+    })
+    .insert_resource(builder_data)
+    .insert_resource(dungeon_data)
+    .insert_resource(prefab_data)
+    .insert_resource(theme_data);
+}
+
+fn read_asset<A: Asset + DeserializeOwned>(file: &str) -> A {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("assets")
+        .join(file);
+    let contents = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Unable to read {}: {e}", path.display()));
+
+    ron::from_str(&contents).unwrap_or_else(|e| panic!("Invalid {}: {e}", path.display()))
+}
@@ -0,0 +1,94 @@
+use bevy_ggrs::ggrs::{Message, NonBlockingSocket};
+use bevy_matchbox::prelude::PeerId;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::sync::{Arc, Mutex};
+
+/// Network conditions simulated by the [`LoopbackNetwork`]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NetworkConditions {
+    /// Ticks (i.e. harness updates) before a message is delivered
+    pub latency: usize,
+    /// Probability of a message being dropped, from 0 to 1
+    pub packet_loss: f64,
+}
+
+/// An in-memory network standing in for the matchbox socket, so several peers
+/// can run in one process. Time only passes when the network [ticks](Self::tick),
+/// and dropped messages are chosen by a seeded generator, so runs repeat.
+#[derive(Clone)]
+pub struct LoopbackNetwork(Arc<Mutex<Network>>);
+
+struct Network {
+    conditions: NetworkConditions,
+    in_flight: Vec<Packet>,
+    rng: Xoshiro256PlusPlus,
+    tick: usize,
+}
+
+struct Packet {
+    deliver_at: usize,
+    from: PeerId,
+    message: Message,
+    to: PeerId,
+}
+
+impl LoopbackNetwork {
+    pub fn new(conditions: NetworkConditions, seed: u64) -> Self {
+        Self(Arc::new(Mutex::new(Network {
+            conditions,
+            in_flight: vec![],
+            rng: Xoshiro256PlusPlus::seed_from_u64(seed),
+            tick: 0,
+        })))
+    }
+
+    /// A socket sending and receiving messages as the given peer
+    pub fn socket(&self, addr: PeerId) -> LoopbackSocket {
+        LoopbackSocket {
+            addr,
+            network: self.clone(),
+        }
+    }
+
+    pub fn tick(&self) {
+        self.0.lock().expect("Network poisoned").tick += 1;
+    }
+}
+
+pub struct LoopbackSocket {
+    addr: PeerId,
+    network: LoopbackNetwork,
+}
+
+impl NonBlockingSocket<PeerId> for LoopbackSocket {
+    fn send_to(&mut self, msg: &Message, addr: &PeerId) {
+        let mut network = self.network.0.lock().expect("Network poisoned");
+        let packet_loss = network.conditions.packet_loss;
+        if packet_loss > 0. && network.rng.gen_bool(packet_loss) {
+            return;
+        }
+
+        let deliver_at = network.tick + network.conditions.latency;
+        network.in_flight.push(Packet {
+            deliver_at,
+            from: self.addr,
+            message: msg.clone(),
+            to: *addr,
+        });
+    }
+
+    fn receive_all_messages(&mut self) -> Vec<(PeerId, Message)> {
+        let mut network = self.network.0.lock().expect("Network poisoned");
+        let tick = network.tick;
+        let (delivered, in_flight) = std::mem::take(&mut network.in_flight)
+            .into_iter()
+            .partition(|p| p.to == self.addr && p.deliver_at <= tick);
+        network.in_flight = in_flight;
+
+        delivered
+            .into_iter()
+            .map(|p: Packet| (p.from, p.message))
+            .collect()
+    }
+}
@@ -0,0 +1,267 @@
+use super::{
+    component_checksums::{record_component_checksums, ComponentChecksums},
+    HeadlessApp, LoopbackNetwork, LoopbackSocket, NetworkConditions,
+};
+use crate::{
+    common::DesyncEvent,
+    player::{MoveDirection, PlayerAction},
+    prelude::*,
+    replay::Replay,
+};
+use bevy_ggrs::{
+    ggrs::{DesyncDetection, P2PSession, PlayerType, SessionBuilder},
+    AdvanceWorld, AdvanceWorldSet, Session,
+};
+use bevy_matchbox::prelude::PeerId;
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+const NUM_PEERS: usize = 2;
+
+/// Real time allowed for the peers to reach the target frame, which mostly
+/// goes to GGRS retrying lost messages.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Longest a scripted action is held, in frames
+const MAX_HOLD_FRAMES: usize = 30;
+
+/// Runs two full [`HeadlessApp`] peers of a P2P session in one process over a
+/// [`LoopbackNetwork`], driving both players with the same random script.
+/// Fails on the first confirmed frame where the peers' states differ, naming the
+/// first component that diverged, so nondeterminism shows up in `cargo test`
+/// rather than in a game.
+pub struct TwoPeerHarness {
+    network: LoopbackNetwork,
+    peers: Vec<HeadlessApp>,
+    /// Last frame of each peer whose state will no longer change on rollback
+    settled: Vec<i32>,
+}
+
+/// The first frame where the peers' states differ.
+#[derive(Debug)]
+pub struct PeerDesync {
+    /// First component (or resource) whose checksum differs. Unknown when only
+    /// GGRS detected the desync.
+    pub component: Option<&'static str>,
+    pub frame: i32,
+}
+
+impl fmt::Display for PeerDesync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.component {
+            Some(component) => write!(
+                f,
+                "Peers diverged at frame {} starting with {component}",
+                self.frame
+            ),
+            None => write!(f, "GGRS detected a desync at frame {}", self.frame),
+        }
+    }
+}
+
+impl TwoPeerHarness {
+    /// Connect two peers playing the dungeon for the seed, with a random
+    /// script of the given number of frames (idle after that).
+    pub fn new(seed: u64, conditions: NetworkConditions, frames: usize) -> Self {
+        Self::with_script(conditions, random_script(seed, frames))
+    }
+
+    /// Connect two peers playing the dungeon for the script's seed, with the
+    /// scripted inputs (idle after that).
+    pub fn with_script(conditions: NetworkConditions, script: Replay) -> Self {
+        let network = LoopbackNetwork::new(conditions, script.seed);
+        let addrs = (0..NUM_PEERS)
+            .map(|i| PeerId::from(Uuid::from_u128(i as u128 + 1)))
+            .collect::<Vec<_>>();
+
+        let peers = (0..NUM_PEERS)
+            .map(|local| {
+                let session = build_session(&addrs, local, network.socket(addrs[local]));
+                let mut peer = HeadlessApp::new_peer(session, script.clone());
+                peer.app_mut()
+                    .init_resource::<ComponentChecksums>()
+                    .add_systems(
+                        AdvanceWorld,
+                        record_component_checksums.in_set(AdvanceWorldSet::Last),
+                    );
+
+                peer
+            })
+            .collect();
+
+        Self {
+            network,
+            peers,
+            settled: vec![0; NUM_PEERS],
+        }
+    }
+
+    pub fn peer_mut(&mut self, index: usize) -> &mut HeadlessApp {
+        &mut self.peers[index]
+    }
+
+    /// Run until both peers reach the frame, comparing them on every frame both
+    /// have confirmed.
+    pub fn run(&mut self, frames: i32) -> Result<(), PeerDesync> {
+        let deadline = Instant::now() + TIMEOUT;
+
+        while self.peers.iter().any(|peer| peer.frame() < frames) {
+            assert!(
+                Instant::now() < deadline,
+                "Peers stalled at frames {:?}",
+                self.peers
+                    .iter()
+                    .map(HeadlessApp::frame)
+                    .collect::<Vec<_>>()
+            );
+
+            self.network.tick();
+            self.update_peers();
+            self.compare()?;
+            self.check_ggrs_desync()?;
+        }
+
+        Ok(())
+    }
+
+    fn check_ggrs_desync(&self) -> Result<(), PeerDesync> {
+        self.peers
+            .iter()
+            .filter_map(|peer| {
+                peer.world()
+                    .resource::<Events<DesyncEvent>>()
+                    .iter_current_update_events()
+                    .next()
+            })
+            .map(|event| PeerDesync {
+                component: None,
+                frame: event.frame,
+            })
+            .next()
+            .map_or(Ok(()), Err)
+    }
+
+    /// Compare the checksums of the frames both peers have settled, then
+    /// discard them.
+    fn compare(&mut self) -> Result<(), PeerDesync> {
+        let settled = *self.settled.iter().min().expect("No peers");
+        let [first, second] = [0, 1].map(|i| {
+            let mut checksums = self.peers[i]
+                .world_mut()
+                .resource_mut::<ComponentChecksums>();
+            let later = checksums.0.split_off(&(settled + 1));
+
+            std::mem::replace(&mut checksums.0, later)
+        });
+
+        first
+            .iter()
+            .filter_map(|(frame, checksums)| Some((frame, checksums, second.get(frame)?)))
+            .find_map(|(&frame, checksums, other)| {
+                checksums
+                    .iter()
+                    .zip(other)
+                    .find(|(a, b)| a != b)
+                    .map(|((component, _), _)| PeerDesync {
+                        component: Some(component),
+                        frame,
+                    })
+            })
+            .map_or(Ok(()), Err)
+    }
+
+    /// Update each peer. GGRS rolls back mispredicted frames as it advances, so
+    /// once a peer advances, the frames whose inputs it had confirmed are
+    /// settled.
+    fn update_peers(&mut self) {
+        for (peer, settled) in self.peers.iter_mut().zip(self.settled.iter_mut()) {
+            let confirmed = confirmed_frame(peer);
+            let frame = peer.frame();
+            peer.update();
+
+            if peer.frame() > frame {
+                *settled = confirmed;
+            }
+        }
+    }
+}
+
+fn build_session(
+    addrs: &[PeerId],
+    local: usize,
+    socket: LoopbackSocket,
+) -> P2PSession<config::GgrsSessionConfig> {
+    let mut session_builder = SessionBuilder::<config::GgrsSessionConfig>::new()
+        .with_num_players(addrs.len())
+        .with_max_prediction_window(config::GGRS_MAX_PREDICTION)
+        .with_desync_detection_mode(DesyncDetection::On { interval: 1 })
+        .with_input_delay(config::GGRS_INPUT_DELAY);
+
+    for (handle, addr) in addrs.iter().enumerate() {
+        let player = if handle == local {
+            PlayerType::Local
+        } else {
+            PlayerType::Remote(*addr)
+        };
+        session_builder = session_builder
+            .add_player(player, handle)
+            .expect("Failed to add player");
+    }
+
+    session_builder
+        .start_p2p_session(socket)
+        .expect("Failed to start session")
+}
+
+/// Random actions for every player, each held for a random number of frames
+/// like a key held down. Excludes actions that only affect the camera or
+/// debugging, or that pause or restart the game.
+pub fn random_script(seed: u64, frames: usize) -> Replay {
+    let actions = [
+        PlayerAction::None,
+        PlayerAction::Move(MoveDirection::Up),
+        PlayerAction::Move(MoveDirection::Down),
+        PlayerAction::Move(MoveDirection::Left),
+        PlayerAction::Move(MoveDirection::Right),
+        PlayerAction::StopMoving,
+        PlayerAction::GrabItem,
+        PlayerAction::UseItem(0),
+        PlayerAction::UseItem(1),
+    ];
+    let mut rng = RandomGenerator::from_seed(seed);
+    let mut script = Replay::new(seed, NUM_PEERS);
+
+    let per_player = (0..NUM_PEERS)
+        .map(|_| {
+            let mut inputs = Vec::with_capacity(frames);
+            while inputs.len() < frames {
+                let action: u8 = actions[rng.gen_range(0..actions.len())].into();
+                let hold = rng
+                    .gen_range(1..MAX_HOLD_FRAMES + 1)
+                    .min(frames - inputs.len());
+                inputs.extend(std::iter::repeat_n(action, hold));
+            }
+
+            inputs
+        })
+        .collect::<Vec<_>>();
+
+    script.inputs = (0..frames)
+        .map(|frame| per_player.iter().map(|inputs| inputs[frame]).collect())
+        .collect();
+
+    script
+}
+
+fn confirmed_frame(peer: &HeadlessApp) -> i32 {
+    match peer
+        .world()
+        .resource::<Session<config::GgrsSessionConfig>>()
+    {
+        Session::P2P(session) => session.confirmed_frame(),
+        _ => unreachable!("Peers run P2P sessions"),
+    }
+}
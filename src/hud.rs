@@ -3,6 +3,25 @@ mod camera_setup;
 mod components;
 mod config;
 
+mod end_screen {
+    mod game_over;
+    mod game_won;
+    mod spawn_end_screen;
+
+    pub(super) use game_over::spawn_game_over_screen;
+    pub(super) use game_won::spawn_game_won_screen;
+
+    pub(super) use spawn_end_screen::spawn_screen;
+
+    use super::*;
+    use spawn_end_screen::spawn_end_screen;
+}
+
+mod lobby;
+mod main_menu;
+mod pause_menu;
+mod peer_status;
+
 mod health {
     mod components;
     mod health_bar;
@@ -30,6 +49,18 @@ mod inventory {
     use hud_inventory_sync::*;
 }
 
+mod level {
+    mod components;
+    mod spawn;
+    mod update;
+
+    pub(super) use spawn::spawn_level_ui;
+    pub(super) use update::update_level;
+
+    use super::*;
+    use components::*;
+}
+
 mod plugin;
 mod tooltips {
     mod components;
@@ -45,6 +76,7 @@ mod tooltips {
 
     pub use components::TooltipLabel;
     pub(super) use plugin::{TooltipCoreSet, TooltipPlugin};
+    pub(super) use toggle::TooltipToggleTrigger;
 
     use super::*;
     use components::*;
@@ -72,12 +104,19 @@ mod weapon {
 
 pub use assets::FontAssets;
 pub use inventory::{InventoryItem, InventoryItemBundle};
+pub use pause_menu::{PauseMenu, Settings};
 pub use plugin::{HudCoreSet, HudPlugin};
 pub use tooltips::TooltipLabel;
 
 use camera_setup::setup_camera;
 use components::*;
+use end_screen::*;
 use health::*;
 use inventory::{spawn_inventory_ui, update_inventory};
-use tooltips::{TooltipCoreSet, TooltipPlugin};
+use level::*;
+use lobby::{despawn_lobby, spawn_lobby, spawn_waiting_screen};
+use main_menu::{despawn_main_menu, spawn_main_menu};
+use pause_menu::{navigate_pause_menu, paused, update_pause_menu};
+use peer_status::update_peer_status;
+use tooltips::{TooltipCoreSet, TooltipPlugin, TooltipToggleTrigger};
 use weapon::*;
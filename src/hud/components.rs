@@ -2,3 +2,19 @@ use bevy::prelude::*;
 
 #[derive(Component)]
 pub struct HudCamera;
+
+/// Marks the lobby and the screen waiting for players to join a room.
+#[derive(Component)]
+pub struct LobbyScreen;
+
+/// Marks the screen reporting interrupted or disconnected peers.
+#[derive(Component)]
+pub struct PeerStatusScreen;
+
+/// Marks the pause menu so it can be despawned on resume or a change of page.
+#[derive(Component)]
+pub struct PauseScreen;
+
+/// Marks the main menu screen so it can be despawned once a game mode is chosen.
+#[derive(Component)]
+pub struct MainMenuScreen;
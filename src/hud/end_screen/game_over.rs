@@ -0,0 +1,28 @@
+use super::{spawn_end_screen, FontAssets};
+use crate::{game_states::GameOverReport, player::LocalPlayer, prelude::*};
+use bevy_ggrs::LocalPlayers;
+
+/// Show how the player died along with their stats.
+pub fn spawn_game_over_screen(
+    font_assets: Res<FontAssets>,
+    local_players: Res<LocalPlayers>,
+    mut commands: Commands,
+    report: Res<GameOverReport>,
+) {
+    let victim = if LocalPlayer::is_local_player_id(report.player_id, &local_players) {
+        "You were".to_string()
+    } else {
+        format!("Player {} was", report.player_id)
+    };
+
+    spawn_end_screen(
+        &mut commands,
+        &font_assets,
+        "GAME OVER",
+        vec![
+            format!("{victim} killed by a {}.", report.cause.name()),
+            format!("Turns survived: {}", report.turns),
+            format!("Monsters killed: {}", report.monsters_killed),
+        ],
+    );
+}
@@ -0,0 +1,24 @@
+use super::{spawn_end_screen, FontAssets};
+use crate::{game_states::Winner, player::LocalPlayer, prelude::*};
+use bevy_ggrs::LocalPlayers;
+
+/// Announce who found the amulet.
+pub fn spawn_game_won_screen(
+    font_assets: Res<FontAssets>,
+    local_players: Res<LocalPlayers>,
+    mut commands: Commands,
+    winner: Res<Winner>,
+) {
+    let finder = if LocalPlayer::is_local_player_id(winner.player_id, &local_players) {
+        "You".to_string()
+    } else {
+        format!("Player {}", winner.player_id)
+    };
+
+    spawn_end_screen(
+        &mut commands,
+        &font_assets,
+        "VICTORY",
+        vec![format!("{finder} found the Amulet of Yala!")],
+    );
+}
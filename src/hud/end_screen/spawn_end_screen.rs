@@ -0,0 +1,68 @@
+use super::{config, FontAssets};
+use crate::prelude::*;
+use bevy::render::view::RenderLayers;
+
+/// Cover the screen with a title and lines of text, followed by restart instructions.
+pub(super) fn spawn_end_screen(
+    commands: &mut Commands,
+    font_assets: &FontAssets,
+    title: &str,
+    lines: Vec<String>,
+) {
+    let lines = lines
+        .into_iter()
+        .chain(std::iter::once("Press R to play again".to_string()))
+        .collect();
+
+    spawn_screen(commands, font_assets, title, lines, InGameEntity);
+}
+
+/// Cover the screen with a title and lines of text. The marker (one or more
+/// components) identifies the screen so it can be despawned.
+pub fn spawn_screen(
+    commands: &mut Commands,
+    font_assets: &FontAssets,
+    title: &str,
+    lines: Vec<String>,
+    marker: impl Bundle,
+) {
+    let text_font = |font_size| TextFont {
+        font: font_assets.hud_font.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            BackgroundColor(config::END_SCREEN_BACKGROUND_COLOR.into()),
+            marker,
+            Node {
+                align_items: AlignItems::Center,
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                height: Val::Percent(100.),
+                justify_content: JustifyContent::Center,
+                position_type: PositionType::Absolute,
+                row_gap: Val::Px(config::MARGIN),
+                width: Val::Percent(100.),
+                ..default()
+            },
+            RenderLayers::layer(config::CAMERA_RENDER_LAYER),
+            GlobalZIndex(config::Z_INDEX),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(title),
+                TextColor(config::TEXT_COLOR.into()),
+                text_font(config::END_SCREEN_TITLE_SIZE),
+            ));
+
+            lines.into_iter().for_each(|line| {
+                parent.spawn((
+                    Text::new(line),
+                    TextColor(config::TEXT_COLOR.into()),
+                    text_font(config::TEXT_SIZE),
+                ));
+            });
+        });
+}
@@ -1,5 +1,6 @@
 use super::{config, FontAssets, HealthBar, HealthPointsText};
 use crate::config as player_config;
+use crate::game_states::InGameEntity;
 use bevy::{color::palettes::css::GRAY, prelude::*, render::view::RenderLayers};
 
 pub fn setup_health_bar(mut commands: Commands, font_assets: Res<FontAssets>) {
@@ -29,6 +30,7 @@ pub fn setup_health_bar(mut commands: Commands, font_assets: Res<FontAssets>) {
                 ..default()
             },
             BackgroundColor(config::BACKGROUND_COLOR.into()),
+            InGameEntity,
             RenderLayers::layer(config::CAMERA_RENDER_LAYER),
             GlobalZIndex(0),
         ))
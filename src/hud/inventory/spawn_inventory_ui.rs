@@ -13,6 +13,7 @@ pub fn spawn_inventory_ui(mut commands: Commands, font_assets: Res<FontAssets>)
     commands
         .spawn((
             InventoryPanel,
+            InGameEntity,
             BackgroundColor(config::BACKGROUND_COLOR.into()),
             Node {
                 align_items: AlignItems::Stretch,
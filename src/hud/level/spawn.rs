@@ -0,0 +1,53 @@
+use super::{config, FontAssets, LevelText};
+use crate::prelude::*;
+use bevy::render::view::RenderLayers;
+
+/// Show the dungeon level and the random seed, so players can share a dungeon
+/// and P2P peers can confirm theirs match.
+pub fn spawn_level_ui(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    rng: Res<RandomGenerator>,
+) {
+    commands
+        .spawn((
+            BackgroundColor(config::BACKGROUND_COLOR.into()),
+            Node {
+                bottom: Val::Px(0.),
+                display: Display::Flex,
+                margin: UiRect::all(Val::Px(config::MARGIN)),
+                padding: UiRect::horizontal(Val::Px(config::MARGIN)),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            InGameEntity,
+            RenderLayers::layer(config::CAMERA_RENDER_LAYER),
+            GlobalZIndex(0),
+        ))
+        .with_children(|parent| {
+            let font = TextFont {
+                font: font_assets.hud_font.clone(),
+                font_size: config::TEXT_SIZE,
+                ..default()
+            };
+
+            parent.spawn((
+                LevelText,
+                Text::new("Level: 1"),
+                TextColor(config::TEXT_COLOR.into()),
+                font.clone(),
+                GlobalZIndex(config::Z_INDEX),
+            ));
+            // The seed only changes on restart, which respawns the HUD
+            parent.spawn((
+                Node {
+                    margin: UiRect::left(Val::Px(config::MARGIN)),
+                    ..default()
+                },
+                Text::new(format!("Seed: {}", rng.seed)),
+                TextColor(config::TEXT_COLOR.into()),
+                font,
+                GlobalZIndex(config::Z_INDEX),
+            ));
+        });
+}
@@ -0,0 +1,7 @@
+use super::LevelText;
+use crate::prelude::*;
+
+/// Show the current dungeon level whenever a new level is spawned.
+pub fn update_level(dungeon: Res<DungeonMap>, mut level_text: Query<&mut Text, With<LevelText>>) {
+    level_text.single_mut().0 = format!("Level: {}", dungeon.level);
+}
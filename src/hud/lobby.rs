@@ -0,0 +1,56 @@
+use super::{spawn_screen, FontAssets, LobbyScreen};
+use crate::{
+    prelude::*,
+    startup::{MatchboxRoom, RoomCodeInput},
+};
+
+/// Show the room code typed so far and the number of players, redrawing the
+/// lobby whenever either changes.
+pub fn spawn_lobby(
+    font_assets: Res<FontAssets>,
+    input: Res<RoomCodeInput>,
+    mut commands: Commands,
+    num_players: Res<NumPlayers>,
+    screens: Query<Entity, With<LobbyScreen>>,
+) {
+    despawn_lobby(commands.reborrow(), screens);
+
+    let placeholders = "_".repeat(config::ROOM_CODE_LENGTH - input.0.len());
+
+    spawn_screen(
+        &mut commands,
+        &font_assets,
+        "LOBBY",
+        vec![
+            "Type a room code and press Enter to join a game".to_string(),
+            "or just press Enter to host a new game".to_string(),
+            format!("Room code: {}{placeholders}", input.0),
+            format!("Players: {} (Up/Down to change)", num_players.0),
+        ],
+        LobbyScreen,
+    );
+}
+
+/// Show the room code to share while waiting for the other players to join.
+pub fn spawn_waiting_screen(
+    font_assets: Res<FontAssets>,
+    mut commands: Commands,
+    room: Res<MatchboxRoom>,
+) {
+    spawn_screen(
+        &mut commands,
+        &font_assets,
+        "WAITING FOR PLAYERS",
+        vec![
+            format!("Room code: {} for {} players", room.code, room.num_players),
+            "Share the room code with the other players".to_string(),
+        ],
+        LobbyScreen,
+    );
+}
+
+pub fn despawn_lobby(mut commands: Commands, screens: Query<Entity, With<LobbyScreen>>) {
+    screens
+        .iter()
+        .for_each(|screen| commands.entity(screen).despawn_recursive());
+}
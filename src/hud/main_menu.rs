@@ -0,0 +1,31 @@
+use super::{spawn_screen, FontAssets, MainMenuScreen};
+use crate::{prelude::*, startup::LaunchOptions};
+
+/// Let the player choose the game mode unless it was given at launch.
+pub fn spawn_main_menu(
+    font_assets: Res<FontAssets>,
+    launch_options: Res<LaunchOptions>,
+    mut commands: Commands,
+) {
+    if launch_options.game_mode.is_some() {
+        return;
+    }
+
+    spawn_screen(
+        &mut commands,
+        &font_assets,
+        "DUNGEON CRAWL",
+        vec![
+            "Press 1 for a single player game".to_string(),
+            "Press 2 for a multiplayer game".to_string(),
+            "Press 3 for a GGRS sync test".to_string(),
+        ],
+        MainMenuScreen,
+    );
+}
+
+pub fn despawn_main_menu(mut commands: Commands, screens: Query<Entity, With<MainMenuScreen>>) {
+    screens
+        .iter()
+        .for_each(|screen| commands.entity(screen).despawn_recursive());
+}
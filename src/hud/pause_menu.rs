@@ -0,0 +1,108 @@
+use super::{spawn_screen, FontAssets, PauseScreen, TooltipToggleTrigger};
+use crate::{game_states::Pause, player::LocalPlayer, prelude::*};
+use bevy_ggrs::LocalPlayers;
+
+/// Which page of the pause menu is showing. Local to each player, unlike the
+/// [`Pause`] itself.
+#[derive(Debug, Default, Resource)]
+pub struct PauseMenu {
+    pub settings: bool,
+}
+
+/// Local preferences that do not affect the game state, so peers need not agree.
+#[derive(Debug, Resource)]
+pub struct Settings {
+    pub tooltips: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self { tooltips: true }
+    }
+}
+
+/// Run condition for systems that only apply while the game is paused.
+pub fn paused(pause: Res<Pause>) -> bool {
+    pause.is_paused()
+}
+
+/// Handle the local pause menu options. Resuming and restarting are player
+/// actions instead (see [`crate::player::PlayerAction`]) since all peers must
+/// agree on them.
+pub fn navigate_pause_menu(
+    mut commands: Commands,
+    mut exit: EventWriter<AppExit>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut menu: ResMut<PauseMenu>,
+    mut settings: ResMut<Settings>,
+) {
+    if keys.just_pressed(KeyCode::KeyS) {
+        menu.settings = !menu.settings;
+    } else if keys.just_pressed(KeyCode::KeyQ) {
+        info!("Quitting game");
+        exit.send(AppExit::Success);
+    } else if menu.settings && keys.just_pressed(KeyCode::KeyT) {
+        settings.tooltips = !settings.tooltips;
+        if !settings.tooltips {
+            commands.trigger(TooltipToggleTrigger::Hide);
+        }
+    }
+}
+
+/// Cover the screen with the pause menu while the game is paused.
+pub fn update_pause_menu(
+    font_assets: Res<FontAssets>,
+    local_players: Res<LocalPlayers>,
+    mut commands: Commands,
+    mut menu: ResMut<PauseMenu>,
+    pause: Res<Pause>,
+    screens: Query<Entity, With<PauseScreen>>,
+    settings: Res<Settings>,
+) {
+    screens
+        .iter()
+        .for_each(|screen| commands.entity(screen).despawn_recursive());
+
+    let Some(player_id) = pause.paused_by else {
+        if menu.settings {
+            menu.settings = false;
+        }
+        return;
+    };
+
+    if menu.settings {
+        let tooltips = if settings.tooltips { "off" } else { "on" };
+        let lines = vec![
+            format!("Press T to turn tooltips {tooltips}"),
+            "Press S to go back".to_string(),
+        ];
+
+        return spawn_screen(
+            &mut commands,
+            &font_assets,
+            "SETTINGS",
+            lines,
+            (InGameEntity, PauseScreen),
+        );
+    }
+
+    let paused_by = (!LocalPlayer::is_local_player_id(player_id, &local_players))
+        .then(|| format!("Player {player_id} paused the game"));
+    let lines = paused_by
+        .into_iter()
+        .chain([
+            "Press Esc to resume".to_string(),
+            "Press R to restart".to_string(),
+            "Press S for settings".to_string(),
+            "Press Q to quit".to_string(),
+        ])
+        .collect();
+
+    spawn_screen(
+        &mut commands,
+        &font_assets,
+        "PAUSED",
+        lines,
+        (InGameEntity, PauseScreen),
+    );
+}
@@ -0,0 +1,52 @@
+use super::{spawn_screen, FontAssets, PeerStatusScreen};
+use crate::{prelude::*, startup::PeerConnections};
+
+/// Cover the screen while waiting for interrupted peers to reconnect, and
+/// report players dropped after a disconnect until the local player responds.
+pub fn update_peer_status(
+    font_assets: Res<FontAssets>,
+    mut commands: Commands,
+    peer_connections: Res<PeerConnections>,
+    screens: Query<Entity, With<PeerStatusScreen>>,
+) {
+    screens
+        .iter()
+        .for_each(|screen| commands.entity(screen).despawn_recursive());
+
+    if !peer_connections.interrupted.is_empty() {
+        let lines = peer_connections
+            .interrupted
+            .iter()
+            .map(|player_id| format!("Waiting for player {player_id} to reconnect..."))
+            .chain(std::iter::once(format!(
+                "Players are dropped after {} seconds",
+                config::GGRS_DISCONNECT_TIMEOUT_SECONDS
+            )))
+            .collect();
+
+        spawn_screen(
+            &mut commands,
+            &font_assets,
+            "CONNECTION INTERRUPTED",
+            lines,
+            PeerStatusScreen,
+        );
+    } else if peer_connections.notice_pending {
+        let lines = peer_connections
+            .disconnected
+            .iter()
+            .map(|player_id| format!("Player {player_id} disconnected and left the game"))
+            .chain(std::iter::once(
+                "Press C to continue or L to return to the lobby".to_string(),
+            ))
+            .collect();
+
+        spawn_screen(
+            &mut commands,
+            &font_assets,
+            "PLAYER LEFT",
+            lines,
+            PeerStatusScreen,
+        );
+    }
+}
@@ -1,8 +1,13 @@
 use super::*;
-use crate::{common, prelude::*};
+use crate::{
+    common,
+    game_states::Pause,
+    prelude::*,
+    startup::{PeerConnections, RoomCodeInput},
+};
 
 #[derive(SystemSet, Clone, Copy, Debug, Hash, PartialEq, Eq)]
-pub struct HudStartupSet;
+pub struct SpawnHudSet;
 
 #[derive(SystemSet, Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct HudCoreSet;
@@ -11,21 +16,67 @@ pub struct HudPlugin;
 
 impl Plugin for HudPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(TooltipPlugin).add_systems(
-            OnEnter(GameState::Startup),
-            (
-                setup_camera,
-                setup_health_bar,
-                spawn_inventory_ui,
-                spawn_weapon_ui,
+        app.init_resource::<PauseMenu>()
+            .init_resource::<Settings>()
+            .add_plugins(TooltipPlugin)
+            .add_systems(
+                OnEnter(GameState::MainMenu),
+                (setup_camera, spawn_main_menu).chain(),
             )
-                .chain()
-                .in_set(HudStartupSet),
-        );
+            .add_systems(OnExit(GameState::MainMenu), despawn_main_menu)
+            .add_systems(OnEnter(GameState::Lobby), spawn_lobby)
+            .add_systems(
+                Update,
+                spawn_lobby.run_if(
+                    in_state(GameState::Lobby)
+                        .and(resource_changed::<RoomCodeInput>.or(resource_changed::<NumPlayers>)),
+                ),
+            )
+            .add_systems(OnExit(GameState::Lobby), despawn_lobby)
+            .add_systems(
+                OnEnter(GameState::Startup),
+                spawn_waiting_screen.run_if(game_mode(GameMode::MultiPlayer)),
+            )
+            .add_systems(OnExit(GameState::Startup), despawn_lobby)
+            .add_systems(
+                Update,
+                update_peer_status.run_if(resource_changed::<PeerConnections>),
+            )
+            .add_systems(
+                Update,
+                (
+                    navigate_pause_menu.run_if(paused),
+                    update_pause_menu.run_if(
+                        resource_changed::<Pause>
+                            .or(resource_changed::<PauseMenu>)
+                            .or(resource_changed::<Settings>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(
+                OnEnter(GameState::InGame),
+                (
+                    setup_health_bar,
+                    spawn_inventory_ui,
+                    spawn_weapon_ui,
+                    spawn_level_ui,
+                )
+                    .chain()
+                    .in_set(SpawnHudSet),
+            )
+            .add_systems(OnEnter(GameState::GameOver), spawn_game_over_screen)
+            .add_systems(OnEnter(GameState::GameWon), spawn_game_won_screen);
 
         common::add_core_systems(
             app,
-            (health_bar, update_inventory, wield_weapon)
+            (
+                health_bar,
+                update_inventory,
+                wield_weapon,
+                update_level.run_if(resource_changed::<DungeonMap>),
+            )
                 .chain()
                 .after(TooltipCoreSet)
                 .in_set(HudCoreSet),
@@ -11,7 +11,7 @@ pub struct TooltipPlugin;
 impl Plugin for TooltipPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(toggle_tooltip)
-            .add_systems(OnEnter(GameState::Startup), spawn_tooltip);
+            .add_systems(OnEnter(GameState::InGame), spawn_tooltip);
 
         common::add_core_systems(
             app,
@@ -22,6 +22,7 @@ impl Plugin for TooltipPlugin {
                 on_zoom.run_if(on_event::<ZoomEvent>),
             )
                 .chain()
+                .run_if(|settings: Res<Settings>| settings.tooltips)
                 .in_set(TooltipCoreSet),
         );
     }
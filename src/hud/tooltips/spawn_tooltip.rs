@@ -1,10 +1,12 @@
 use super::{config, toggle_tooltip, FontAssets, TooltipUI};
+use crate::game_states::InGameEntity;
 use bevy::{prelude::*, render::view::RenderLayers};
 
 pub fn spawn_tooltip(mut commands: Commands, font_assets: Res<FontAssets>) {
     commands
         .spawn((
             TooltipUI::default(),
+            InGameEntity,
             Text::new(String::new()),
             BackgroundColor(config::BACKGROUND_COLOR.into()),
             TextColor(config::TEXT_COLOR.into()),
@@ -9,7 +9,7 @@ pub(super) use display_info::*;
 pub(super) use factory::TooltipToggleFactory;
 pub(super) use shower::*;
 pub(super) use tooltip_info::TooltipInfo;
-pub(super) use trigger::TooltipToggleTrigger;
+pub(in crate::hud) use trigger::TooltipToggleTrigger;
 pub(super) use trigger_builder::MouseBasedTooltipToggleFactory;
 
 use super::queries::*;
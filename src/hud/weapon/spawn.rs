@@ -15,6 +15,7 @@ pub fn spawn_weapon_ui(mut commands: Commands, font_assets: Res<FontAssets>) {
                 width: Val::Percent(100.),
                 ..default()
             },
+            InGameEntity,
             RenderLayers::layer(config::CAMERA_RENDER_LAYER),
             GlobalZIndex(0),
         ))
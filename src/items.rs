@@ -1,6 +1,7 @@
 mod components;
+mod despawn_items;
 mod plugin;
 mod spawn_items;
 
-pub use components::{Grabbable, MagicItem, MagicItemTemplate, Weapon};
+pub use components::{Grabbable, MagicItem, MagicItemKind, MagicItemTemplate, Weapon};
 pub use plugin::ItemsPlugin;
@@ -1,4 +1,8 @@
-use crate::{hud::TooltipLabel, prelude::*};
+use crate::{
+    common::{deserialize_sprite, SpriteAssets},
+    hud::TooltipLabel,
+    prelude::*,
+};
 use serde::Deserialize;
 
 #[derive(Bundle)]
@@ -12,17 +16,13 @@ pub struct MagicItemBundle {
 }
 
 impl MagicItemBundle {
-    pub fn new(template: &MagicItemTemplate, pos: Vec2) -> Self {
+    pub fn new(template: &MagicItemTemplate, pos: Vec2, sprite_assets: &SpriteAssets) -> Self {
         let item = template.item;
 
         Self {
             item,
             grabbable: Grabbable,
-            sprite: Sprite {
-                color: template.color(),
-                custom_size: Some(Vec2::new(TILE_WIDTH, TILE_HEIGHT)),
-                ..default()
-            },
+            sprite: sprite_assets.sprite(template.sprite, template.color()),
             tooltip_label: TooltipLabel(item.label()),
             transform: Transform::from_translation(pos.extend(config::ITEM_Z_LAYER)),
             visibility: Visibility::Hidden,
@@ -30,13 +30,16 @@ impl MagicItemBundle {
     }
 }
 
-#[derive(Component, Clone, Copy, Hash)]
+#[derive(Component, Clone, Copy, Debug, Hash)]
 pub struct Grabbable;
 
 #[derive(Debug, Deserialize)]
 pub struct MagicItemTemplate {
     pub frequency: usize,
     pub item: MagicItem,
+    /// Index into the SpriteAssets atlas. Rendered as a square of the color if missing.
+    #[serde(default, deserialize_with = "deserialize_sprite")]
+    pub sprite: Option<usize>,
     color: Srgba,
 }
 
@@ -48,11 +51,23 @@ impl MagicItemTemplate {
 
 #[derive(Component, Clone, Debug, Deserialize, Copy, Hash)]
 pub enum MagicItem {
+    /// Grabbing the Amulet of Yala wins the game
+    Amulet,
     HealingPotion(HealthUnit),
     Map,
     Weapon(Weapon),
 }
 
+/// The kind of a [`MagicItem`] regardless of its details (e.g. healing amount),
+/// used by dungeon themes to restrict which items may spawn.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+pub enum MagicItemKind {
+    Amulet,
+    HealingPotion,
+    Map,
+    Weapon,
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Hash)]
 pub enum Sword {
     Huge,
@@ -68,8 +83,18 @@ impl MagicItem {
         }
     }
 
+    pub fn kind(&self) -> MagicItemKind {
+        match self {
+            MagicItem::Amulet => MagicItemKind::Amulet,
+            MagicItem::HealingPotion(_) => MagicItemKind::HealingPotion,
+            MagicItem::Map => MagicItemKind::Map,
+            MagicItem::Weapon(_) => MagicItemKind::Weapon,
+        }
+    }
+
     pub fn label(&self) -> String {
         match self {
+            MagicItem::Amulet => "Amulet of Yala".to_string(),
             MagicItem::HealingPotion(hp) => format!("Healing Potion ({hp} hp)"),
             MagicItem::Map => "Magic Map".to_string(),
             MagicItem::Weapon(w) => format!("{:?} Sword ({} hp)", w.sword, w.damage),
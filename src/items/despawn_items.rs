@@ -0,0 +1,9 @@
+use super::Grabbable;
+use crate::prelude::*;
+
+/// Despawn the items left on the current level before spawning the next one.
+pub fn despawn_items(mut commands: Commands, items: Query<Entity, With<Grabbable>>) {
+    items
+        .iter()
+        .for_each(|item| commands.entity(item).despawn_recursive());
+}
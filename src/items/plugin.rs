@@ -1,5 +1,10 @@
-use super::{spawn_items::spawn_items, Grabbable, MagicItem, Weapon};
-use crate::{monsters::SpawnMonstersSet, prelude::*};
+use super::{despawn_items::despawn_items, spawn_items::spawn_items, Grabbable, MagicItem, Weapon};
+use crate::{
+    common,
+    dungeon::NextLevelEvent,
+    monsters::{MonstersCoreSet, SpawnMonstersSet},
+    prelude::*,
+};
 use bevy::prelude::*;
 use bevy_ggrs::prelude::*;
 
@@ -12,13 +17,20 @@ impl Plugin for ItemsPlugin {
             spawn_items.after(SpawnMonstersSet),
         );
 
-        if !game_mode(GameMode::SinglePlayer) {
-            app.rollback_component_with_copy::<Grabbable>()
-                .checksum_component_with_hash::<Grabbable>()
-                .rollback_component_with_copy::<MagicItem>()
-                .checksum_component_with_hash::<MagicItem>()
-                .rollback_component_with_copy::<Weapon>()
-                .checksum_component_with_hash::<Weapon>();
-        }
+        common::add_core_systems(
+            app,
+            (despawn_items, spawn_items)
+                .chain()
+                .run_if(on_event::<NextLevelEvent>)
+                .after(SpawnMonstersSet)
+                .before(MonstersCoreSet),
+        );
+
+        app.rollback_component_with_copy::<Grabbable>()
+            .checksum_component_with_hash::<Grabbable>()
+            .rollback_component_with_copy::<MagicItem>()
+            .checksum_component_with_hash::<MagicItem>()
+            .rollback_component_with_copy::<Weapon>()
+            .checksum_component_with_hash::<Weapon>();
     }
 }
@@ -1,6 +1,7 @@
-use super::MagicItemTemplate;
+use super::{MagicItem, MagicItemTemplate};
 use crate::{
-    common::{DungeonAssets, DungeonData},
+    common::{DungeonAssets, DungeonData, DungeonTheme, SpriteAssets},
+    dungeon::DungeonPosition,
     items::components::MagicItemBundle,
     prelude::*,
 };
@@ -14,8 +15,13 @@ pub fn spawn_items(
     dungeon_data_assets: Res<Assets<DungeonData>>,
     mut commands: Commands,
     mut rng: ResMut<RandomGenerator>,
+    sprite_assets: Res<SpriteAssets>,
+    theme: Res<DungeonTheme>,
 ) {
-    let item_distribution = create_distribution(dungeon_data_assets.get(&dungeon_assets.data));
+    let dungeon_data = dungeon_data_assets
+        .get(&dungeon_assets.data)
+        .expect("Failed to load dungeon data");
+    let item_distribution = create_distribution(dungeon_data, &theme);
 
     let stats = dungeon
         .item_positions
@@ -26,27 +32,105 @@ pub fn spawn_items(
                 pos.to_vec2(),
             )
         })
-        .map(|(template, pos)| MagicItemBundle::new(template, pos))
+        .map(|(template, pos)| MagicItemBundle::new(template, pos, &sprite_assets))
         .fold(HashMap::new(), |mut acc, item_bundle| {
             acc.entry(item_bundle.item.label())
                 .and_modify(|count| *count += 1)
                 .or_insert(1);
 
-            commands.spawn(item_bundle).add_rollback();
+            commands.spawn((item_bundle, InGameEntity)).add_rollback();
 
             acc
         });
 
     info!("Spawned items: {stats:?}");
+
+    if !dungeon.treasure_positions.is_empty() {
+        spawn_treasure(
+            &dungeon,
+            dungeon_data,
+            &theme,
+            &sprite_assets,
+            &mut commands,
+        );
+    }
+
+    if let Some(amulet_pos) = dungeon.amulet_position() {
+        spawn_amulet(amulet_pos, dungeon_data, &sprite_assets, &mut commands);
+    }
 }
 
 /// Create a distribution of item templates based on their frequency so that
 /// those with a higher frequency are more likely to be randomly selected.
-fn create_distribution(dungeon_data: Option<&DungeonData>) -> Vec<&MagicItemTemplate> {
-    dungeon_data
-        .expect("Failed to load dungeon data")
+/// Only items allowed by the level's theme are included.
+fn create_distribution<'a>(
+    dungeon_data: &'a DungeonData,
+    theme: &DungeonTheme,
+) -> Vec<&'a MagicItemTemplate> {
+    let distribution: Vec<&MagicItemTemplate> = dungeon_data
         .items
         .iter()
+        .filter(|template| theme.allows_item(template.item.kind()))
         .flat_map(|template| repeat(template).take(template.frequency))
-        .collect()
+        .collect();
+    assert!(
+        !distribution.is_empty(),
+        "No items for theme {}",
+        theme.name
+    );
+
+    distribution
+}
+
+fn spawn_amulet(
+    pos: DungeonPosition,
+    dungeon_data: &DungeonData,
+    sprite_assets: &SpriteAssets,
+    commands: &mut Commands,
+) {
+    let template = dungeon_data
+        .items
+        .iter()
+        .find(|t| matches!(t.item, MagicItem::Amulet))
+        .expect("Amulet missing from dungeon data");
+
+    commands
+        .spawn((
+            MagicItemBundle::new(template, pos.to_vec2(), sprite_assets),
+            InGameEntity,
+        ))
+        .add_rollback();
+
+    info!("Spawned the amulet at {pos}");
+}
+
+/// Vault treasure is the rarest (lowest frequency) item allowed by the theme.
+fn spawn_treasure(
+    dungeon: &DungeonMap,
+    dungeon_data: &DungeonData,
+    theme: &DungeonTheme,
+    sprite_assets: &SpriteAssets,
+    commands: &mut Commands,
+) {
+    let template = dungeon_data
+        .items
+        .iter()
+        .filter(|t| t.frequency > 0 && theme.allows_item(t.item.kind()))
+        .min_by_key(|t| t.frequency)
+        .expect("No treasure for theme");
+
+    for pos in &dungeon.treasure_positions {
+        commands
+            .spawn((
+                MagicItemBundle::new(template, pos.to_vec2(), sprite_assets),
+                InGameEntity,
+            ))
+            .add_rollback();
+    }
+
+    info!(
+        "Spawned {} treasure: {}",
+        dungeon.treasure_positions.len(),
+        template.item.label()
+    );
 }
@@ -0,0 +1,50 @@
+pub mod common;
+pub mod config;
+pub mod dungeon;
+pub mod game_states;
+#[cfg(feature = "test-harness")]
+pub mod headless;
+pub mod hud;
+pub mod items;
+pub mod monsters;
+pub mod player;
+pub mod replay;
+pub mod startup;
+
+pub use common::{fov, health};
+
+pub mod prelude {
+    pub use crate::common::RandomGenerator;
+    pub use crate::config::{self, game_mode, GameMode, NumPlayers};
+    pub use crate::dungeon::{DungeonMap, TILE_HEIGHT, TILE_WIDTH};
+    pub use crate::fov::FieldOfView;
+    pub use crate::game_states::{GameState, InGameEntity};
+    pub use crate::health::{Damage, DamageUnit, Healing, Health, HealthUnit};
+    pub use crate::player::Player;
+    pub use bevy::prelude::*;
+}
+
+use bevy::app::PluginGroupBuilder;
+use bevy_ggrs::GgrsPlugin;
+use prelude::*;
+
+/// The gameplay plugins, without the HUD or asset loading, so they can also run
+/// in a `headless::HeadlessApp`.
+pub struct GamePlugins;
+
+impl PluginGroup for GamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(common::CommonPlugin)
+            .add(dungeon::DungeonPlugin)
+            .add(fov::FovPlugin)
+            .add(health::HealthPlugin)
+            .add(items::ItemsPlugin)
+            .add(game_states::GameStatesPlugin)
+            .add(GgrsPlugin::<config::GgrsSessionConfig>::default())
+            .add(monsters::MonstersPlugin)
+            .add(player::PlayerPlugin)
+            .add(replay::ReplayPlugin)
+            .add(startup::StartupPlugin)
+    }
+}
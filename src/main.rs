@@ -1,29 +1,5 @@
-mod common;
-pub mod config;
-mod dungeon;
-mod game_states;
-mod hud;
-mod items;
-mod monsters;
-mod player;
-mod startup;
-
-pub use common::{fov, health};
-
-pub mod prelude {
-    pub use crate::common::RandomGenerator;
-    pub use crate::config::{self, game_mode, GameMode};
-    pub use crate::dungeon::{DungeonMap, TILE_HEIGHT, TILE_WIDTH};
-    pub use crate::fov::FieldOfView;
-    pub use crate::game_states::GameState;
-    pub use crate::health::{Damage, DamageUnit, Healing, Health, HealthUnit};
-    pub use crate::player::Player;
-    pub use bevy::prelude::*;
-}
-
 use bevy::log::LogPlugin;
-use bevy_ggrs::GgrsPlugin;
-use prelude::*;
+use dungeon_crawl_p2p::{hud::HudPlugin, prelude::*, startup, GamePlugins};
 
 fn main() {
     let logging_filter = if config::GGRS_DEBUG {
@@ -33,6 +9,7 @@ fn main() {
     };
 
     App::new()
+        .insert_resource(startup::LaunchOptions::parse())
         .add_plugins((
             DefaultPlugins
                 .set(WindowPlugin {
@@ -48,17 +25,9 @@ fn main() {
                     filter: logging_filter,
                     ..default()
                 }),
-            common::CommonPlugin,
-            dungeon::DungeonPlugin,
-            fov::FovPlugin,
-            health::HealthPlugin,
-            hud::HudPlugin,
-            items::ItemsPlugin,
-            game_states::GameStatesPlugin,
-            GgrsPlugin::<config::GgrsSessionConfig>::default(),
-            monsters::MonstersPlugin,
-            player::PlayerPlugin,
-            startup::StartupPlugin,
+            GamePlugins,
+            HudPlugin,
+            startup::AssetLoadingPlugin,
         ))
         .run();
 }
@@ -1,22 +1,27 @@
 mod components;
+mod despawn_monsters;
 mod events;
 mod monster_actions {
     mod attack_player;
+    mod change_behavior;
     mod do_monsters_action;
     mod monster_action_determiner;
     mod monster_action_params;
     mod monster_move_tracker;
     mod move_monster;
     mod persist_monster_moves;
+    mod remember_target;
     mod update_last_action;
 
     pub(super) use attack_player::attack_player;
+    pub(super) use change_behavior::change_behavior;
     pub(super) use do_monsters_action::do_monsters_action;
     pub(super) use monster_action_determiner::{MonsterAction, MonsterActionDeterminer};
     pub(super) use monster_action_params::*;
     pub(super) use monster_move_tracker::{MonsterMove, MonsterMoveTracker};
     pub(super) use move_monster::move_monster;
     pub(super) use persist_monster_moves::persist_monster_moves;
+    pub(super) use remember_target::{remember_target, tick_target_memory};
     pub(super) use update_last_action::update_last_action;
 
     use super::components::*;
@@ -25,7 +30,7 @@ mod monster_actions {
 mod plugin;
 mod spawn_monsters;
 
-pub use components::{Monster, MonsterBundle, MonsterTemplate};
+pub use components::{Behavior, Monster, MonsterBundle, MonsterTemplate, TargetMemory};
 pub use events::MonsterMovesEvent;
 pub use plugin::{MonstersCoreSet, MonstersPlugin, SpawnMonstersSet};
 
@@ -1,7 +1,11 @@
+mod behavior;
 mod monster;
+mod target_memory;
 
+pub use behavior::{Behavior, BehaviorKind};
 use bevy::prelude::Component;
 pub use monster::{Monster, MonsterBundle, MonsterTemplate};
+pub use target_memory::TargetMemory;
 
 /// LastAction is used to track the time of the last action of a monster so as
 /// to throttle the rate at which monsters can act.
@@ -0,0 +1,53 @@
+use bevy::{math::IVec2, prelude::Component};
+use serde::Deserialize;
+
+/// What a monster does when it is not chasing a player. The default for each
+/// monster type is configured in dungeon.ron.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq)]
+pub enum BehaviorKind {
+    /// Run away from players while health is low
+    Flee,
+    /// Hold a post (the spawn position), returning to it after a chase
+    Guard,
+    /// Walk a loop of waypoints around the spawn position
+    Patrol,
+    /// Sleep until a player gets near
+    Rest,
+    /// Step about randomly
+    #[default]
+    Wander,
+}
+
+/// A monster's behavior state. Transitions are determined by the
+/// `MonsterActionDeterminer` using the seeded `RandomGenerator`, so they are
+/// the same on all p2p clients.
+#[derive(Clone, Component, Debug, Hash)]
+pub struct Behavior {
+    pub current: BehaviorKind,
+    /// Behavior configured for the monster type, resumed after fleeing
+    pub default: BehaviorKind,
+    next_waypoint: usize,
+    pub post: IVec2,
+    waypoints: Vec<IVec2>,
+}
+
+impl Behavior {
+    pub fn new(default: BehaviorKind, post: IVec2, waypoints: Vec<IVec2>) -> Self {
+        Self {
+            current: default,
+            default,
+            next_waypoint: 0,
+            post,
+            waypoints,
+        }
+    }
+
+    /// Head to the next waypoint in the patrol loop.
+    pub fn advance_waypoint(&mut self) {
+        self.next_waypoint = (self.next_waypoint + 1) % self.waypoints.len().max(1);
+    }
+
+    pub fn waypoint(&self) -> Option<IVec2> {
+        self.waypoints.get(self.next_waypoint).copied()
+    }
+}
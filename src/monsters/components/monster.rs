@@ -1,10 +1,16 @@
-use super::LastAction;
-use crate::{hud::TooltipLabel, player::Obstacle, prelude::*};
+use super::{Behavior, BehaviorKind, LastAction};
+use crate::{
+    common::{deserialize_sprite, SpriteAssets},
+    hud::TooltipLabel,
+    player::Obstacle,
+    prelude::*,
+};
 use serde::Deserialize;
 
 #[derive(Bundle)]
 pub struct MonsterBundle {
     pub monster: Monster,
+    pub behavior: Behavior,
     pub damage: Damage,
     pub fov: FieldOfView,
     pub health: Health,
@@ -17,21 +23,24 @@ pub struct MonsterBundle {
 }
 
 impl MonsterBundle {
-    pub fn new(template: &MonsterTemplate, pos: Vec2) -> Self {
+    /// The waypoints are only used if the monster type patrols.
+    pub fn new(
+        template: &MonsterTemplate,
+        pos: Vec2,
+        waypoints: Vec<IVec2>,
+        sprite_assets: &SpriteAssets,
+    ) -> Self {
         let monster = template.monster;
 
         Self {
             monster,
+            behavior: Behavior::new(template.behavior, pos.as_ivec2(), waypoints),
             damage: Damage(template.damage),
             fov: FieldOfView::new(config::MONSTER_FOV_RADIUS),
             health: Health::new(template.health),
             last_action: LastAction::new(),
             obstacle: Obstacle::Monster,
-            sprite: Sprite {
-                color: template.color(),
-                custom_size: Some(Vec2::new(TILE_WIDTH, TILE_HEIGHT)),
-                ..default()
-            },
+            sprite: sprite_assets.sprite(template.sprite, template.color()),
             tooltip_label: TooltipLabel(template.label()),
             transform: Transform::from_translation(pos.extend(config::MONSTER_Z_LAYER)),
             visibility: Visibility::Hidden,
@@ -41,10 +50,15 @@ impl MonsterBundle {
 
 #[derive(Debug, Deserialize)]
 pub struct MonsterTemplate {
+    #[serde(default)]
+    pub behavior: BehaviorKind,
     pub damage: DamageUnit,
     pub frequency: usize,
     pub health: HealthUnit,
     pub monster: Monster,
+    /// Index into the SpriteAssets atlas. Rendered as a square of the color if missing.
+    #[serde(default, deserialize_with = "deserialize_sprite")]
+    pub sprite: Option<usize>,
     color: Srgba,
 }
 
@@ -60,7 +74,7 @@ impl MonsterTemplate {
     }
 }
 
-#[derive(Component, Clone, Copy, Debug, Deserialize, Hash)]
+#[derive(Component, Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
 pub enum Monster {
     Ettin,
     Goblin,
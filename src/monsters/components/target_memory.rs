@@ -0,0 +1,44 @@
+use crate::config;
+use bevy::{
+    math::IVec2,
+    prelude::Component,
+    time::{Timer, TimerMode},
+};
+use std::{
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+
+/// The position where a monster last saw the player it was chasing. The monster
+/// keeps heading there after losing sight of the player until it gets there or
+/// the memory fades.
+#[derive(Clone, Component, Debug)]
+pub struct TargetMemory {
+    pub pos: IVec2,
+    timer: Timer,
+}
+
+impl TargetMemory {
+    pub fn new(pos: IVec2) -> Self {
+        Self {
+            pos,
+            timer: Timer::from_seconds(config::MONSTER_MEMORY_SECONDS, TimerMode::Once),
+        }
+    }
+
+    pub fn just_finished(&self) -> bool {
+        self.timer.just_finished()
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        self.timer.tick(delta);
+    }
+}
+
+impl Hash for TargetMemory {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.pos.hash(state);
+        self.timer.elapsed().as_nanos().hash(state);
+        self.timer.finished().hash(state);
+    }
+}
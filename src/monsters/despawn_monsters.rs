@@ -0,0 +1,9 @@
+use super::Monster;
+use crate::prelude::*;
+
+/// Despawn the monsters of the current level before spawning the next one.
+pub fn despawn_monsters(mut commands: Commands, monsters: Query<Entity, With<Monster>>) {
+    monsters
+        .iter()
+        .for_each(|monster| commands.entity(monster).despawn_recursive());
+}
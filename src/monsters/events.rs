@@ -1,3 +1,4 @@
+use super::components::Behavior;
 use crate::health::DamageUnit;
 use bevy::prelude::*;
 
@@ -13,15 +14,33 @@ impl Plugin for MonstersEventsPlugin {
 
 #[derive(Event)]
 pub struct MonsterActedEvent {
+    /// New behavior state, if it changed
+    pub behavior: Option<Behavior>,
+    pub memory: MemoryUpdate,
     pub monster: Entity,
 }
 
 impl MonsterActedEvent {
-    pub fn new(monster: Entity) -> Self {
-        Self { monster }
+    pub fn new(monster: Entity, memory: MemoryUpdate, behavior: Option<Behavior>) -> Self {
+        Self {
+            behavior,
+            memory,
+            monster,
+        }
     }
 }
 
+/// How acting changes what the monster remembers about its target
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MemoryUpdate {
+    /// Reached the remembered position without seeing the target
+    Forget,
+    #[default]
+    Keep,
+    /// Saw the target at the given position
+    Remember(IVec2),
+}
+
 /// Event: Monster attacks player
 #[derive(Event)]
 pub struct MonsterAttacksEvent {
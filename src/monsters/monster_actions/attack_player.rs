@@ -1,21 +1,37 @@
 use super::MonsterAttacksEvent;
-use crate::prelude::*;
+use crate::{
+    common::{Change, PendingChange},
+    game_states::GameOverReport,
+    monsters::Monster,
+    player::PlayerStats,
+    prelude::*,
+};
 
+/// Damage the attacked players. A lethal hit requests the game over as a
+/// [`PendingChange`], since the hit may be mispredicted and the end of the
+/// game is not rolled back.
 pub fn attack_player(
     mut attack_events: EventReader<MonsterAttacksEvent>,
     mut commands: Commands,
-    mut next_state: ResMut<NextState<GameState>>,
-    mut players: Query<&mut Health, With<Player>>,
+    mut pending: ResMut<PendingChange>,
+    mut players: Query<(&mut Health, &PlayerStats), With<Player>>,
+    monsters: Query<&Monster>,
 ) {
     for event in attack_events.read() {
-        let mut health = players.get_mut(event.player).expect("Inconceivable!");
+        let (mut health, stats) = players.get_mut(event.player).expect("Inconceivable!");
 
         log(&health, event);
 
         if event.damage >= health.current {
             if !config::PLAYER_IMMORTAL {
                 health.current = 0;
-                next_state.set(GameState::GameOver);
+                let monster = monsters.get(event.monster).expect("Inconceivable!");
+                pending.request(Change::GameOver(GameOverReport {
+                    cause: *monster,
+                    monsters_killed: stats.monsters_killed,
+                    player_id: event.player_id,
+                    turns: stats.turns,
+                }));
             }
         } else {
             health.current -= event.damage;
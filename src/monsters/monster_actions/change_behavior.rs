@@ -0,0 +1,21 @@
+use super::{Behavior, MonsterActedEvent};
+use bevy::prelude::*;
+
+/// Apply any behavior state transitions determined for the monsters that acted.
+pub fn change_behavior(
+    mut acted_events: EventReader<MonsterActedEvent>,
+    mut behaviors: Query<&mut Behavior>,
+) {
+    for event in acted_events.read() {
+        if let Some(behavior) = &event.behavior {
+            let mut current = behaviors.get_mut(event.monster).expect("Inconceivable!");
+            if current.current != behavior.current {
+                info!(
+                    "Monster {} changes behavior from {:?} to {:?}",
+                    event.monster, current.current, behavior.current
+                );
+            }
+            *current = behavior.clone();
+        }
+    }
+}
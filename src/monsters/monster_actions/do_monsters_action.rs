@@ -6,28 +6,29 @@ pub fn do_monsters_action(
     mut attack_event: EventWriter<MonsterAttacksEvent>,
     mut move_event: EventWriter<MonsterMovesEvent>,
     mut rng: ResMut<RandomGenerator>,
+    dungeon: Res<DungeonMap>,
     monsters: MonsterQuery,
     players: PlayersQuery,
     time: Res<Time>,
     wall_tiles: WallQuery,
 ) {
-    let mut params = MonsterActionParams::new(&monsters, &players, &wall_tiles);
+    let mut params = MonsterActionParams::new(&dungeon, &monsters, &players, &wall_tiles);
 
     sorted_determiners(&monsters, &time)
         .into_iter()
         .filter(|d| !d.is_throttled())
         .for_each(|mut determiner| {
-            let Some(action) = determiner.determine(&params, &mut rng) else {
-                return;
-            };
+            let action = determiner.determine(&params, &mut rng);
 
             match action {
-                MonsterAction::Attack(e) => {
+                Some(MonsterAction::Attack(e)) => {
                     attack_event.send(e);
                 }
-                MonsterAction::Move(e) => {
+                Some(MonsterAction::Move(e)) => {
                     move_event.send(e);
                 }
+                None if determiner.behavior_changed() => (),
+                None => return,
             };
 
             params.update_monster_position(determiner.old_pos(), determiner.target_pos());
@@ -40,9 +41,7 @@ pub fn do_monsters_action(
 fn sorted_determiners(monsters: &MonsterQuery, time: &Time) -> Vec<MonsterActionDeterminer> {
     let mut monsters: Vec<_> = monsters
         .iter()
-        .map(|(damage, fov, last_action, monster, transform)| {
-            MonsterActionDeterminer::new(damage.0, fov, last_action, monster, time, transform)
-        })
+        .map(|monster| MonsterActionDeterminer::new(monster, time))
         .collect();
     monsters.sort_by_key(|d| d.sort_key());
 
@@ -1,6 +1,11 @@
 use super::*;
-use crate::{common::RandomCounter, prelude::*};
+use crate::{
+    common::RandomCounter,
+    dungeon::{AStarPathFinder, DijkstraDistance, DungeonPosition},
+    prelude::*,
+};
 use bevy::utils::hashbrown::HashSet;
+use std::cmp::Reverse;
 
 pub enum MonsterAction {
     Attack(MonsterAttacksEvent),
@@ -8,38 +13,54 @@ pub enum MonsterAction {
 }
 
 pub struct MonsterActionDeterminer {
+    behavior: Behavior,
+    behavior_changed: bool,
     current_pos: IVec2,
     damage: DamageUnit,
     fov: HashSet<IVec2>,
+    health: Health,
     is_throttled: bool,
+    /// Where the monster last saw its target (if it remembers)
+    memory: Option<IVec2>,
+    memory_update: MemoryUpdate,
     monster: Entity,
     target_pos: IVec2,
 }
 
 impl MonsterActionDeterminer {
     pub fn new(
-        damage: DamageUnit,
-        fov: &FieldOfView,
-        last_action: &LastAction,
-        monster: Entity,
+        (behavior, damage, fov, health, last_action, monster, memory, transform): MonsterQueryItem,
         time: &Time,
-        transform: &Transform,
     ) -> Self {
         let is_throttled =
             time.elapsed_secs() - last_action.time < config::MONSTER_THROTTLE_SECONDS;
+        let current_pos = transform.translation.truncate().as_ivec2();
 
         Self {
-            current_pos: transform.translation.truncate().as_ivec2(),
-            damage,
+            behavior: behavior.clone(),
+            behavior_changed: false,
+            current_pos,
+            damage: damage.0,
             fov: fov.visible_tiles.keys().copied().collect(),
+            health: *health,
             is_throttled,
+            memory: memory.map(|m| m.pos),
+            memory_update: MemoryUpdate::Keep,
             monster,
-            target_pos: IVec2::ZERO,
+            target_pos: current_pos,
         }
     }
 
+    pub fn behavior_changed(&self) -> bool {
+        self.behavior_changed
+    }
+
     pub fn create_acted_event(&self) -> MonsterActedEvent {
-        MonsterActedEvent::new(self.monster)
+        MonsterActedEvent::new(
+            self.monster,
+            self.memory_update,
+            self.behavior_changed.then(|| self.behavior.clone()),
+        )
     }
 
     pub fn determine(
@@ -52,27 +73,38 @@ impl MonsterActionDeterminer {
         }
 
         let MonsterActionParams {
+            dijkstra_maps,
+            dungeon,
             monsters: monster_positions,
             players,
             walls,
         } = params;
 
+        let visible_target = self.try_attack(players);
+        self.update_behavior(visible_target, players, rng);
+
         let valid_moves = self.gather_valid_moves(monster_positions, walls);
         if valid_moves.is_empty() {
             return None;
         }
 
-        self.try_attack(players)
-            .map_or_else(
-                || self.random_move(rng, &valid_moves),
-                |attack_goal| self.chase(attack_goal, &valid_moves),
-            )
-            .map(|target_pos| {
-                self.target_pos = target_pos;
+        match self.behavior.current {
+            BehaviorKind::Flee => {
+                visible_target.and_then(|threat| self.flee(threat, dijkstra_maps, &valid_moves))
+            }
+            BehaviorKind::Rest => None,
+            _ => visible_target.or(self.memory).map_or_else(
+                || self.idle_move(dijkstra_maps, dungeon, rng, &valid_moves),
+                |attack_goal| self.chase(attack_goal, dijkstra_maps, dungeon, &valid_moves),
+            ),
+        }
+        .map(|target_pos| {
+            self.target_pos = target_pos;
+            self.memory_update = self.update_memory(visible_target);
 
-                self.attack(players)
-                    .unwrap_or_else(|| self.move_monster(rng.counter))
-            })
+            self.attack(players)
+                .unwrap_or_else(|| self.move_monster(rng.counter))
+        })
     }
 
     pub fn is_throttled(&self) -> bool {
@@ -83,8 +115,11 @@ impl MonsterActionDeterminer {
         self.current_pos
     }
 
-    pub fn sort_key(&self) -> u32 {
-        self.monster.index()
+    /// Monsters occupy distinct tiles, so their position is a stable sort key
+    /// on all p2p clients. Entity indices are not, since monsters on deeper
+    /// levels are spawned after each client has spawned its own HUD entities.
+    pub fn sort_key(&self) -> (i32, i32) {
+        (self.current_pos.x, self.current_pos.y)
     }
 
     pub fn target_pos(&self) -> IVec2 {
@@ -98,12 +133,30 @@ impl MonsterActionDeterminer {
             .map(MonsterAction::Attack)
     }
 
-    fn chase(&self, attack_goal: IVec2, valid_moves: &[IVec2]) -> Option<IVec2> {
+    /// Step downhill on the target player's [`DijkstraMap`] so as to route
+    /// around walls. Ties (and unreachable tiles) fall back to the straight-line
+    /// distance and then to the fixed order of the valid moves, so the choice is
+    /// the same on all p2p clients. Goals other than a player's position have
+    /// no [`DijkstraMap`] and are reached via [`Self::step_towards`] instead.
+    fn chase(
+        &self,
+        attack_goal: IVec2,
+        dijkstra_maps: &DijkstraMaps,
+        dungeon: &DungeonMap,
+        valid_moves: &[IVec2],
+    ) -> Option<IVec2> {
+        let Some(dijkstra_map) = dijkstra_maps.get(&attack_goal) else {
+            return self.step_towards(attack_goal, dungeon, valid_moves);
+        };
+
         valid_moves
             .iter()
-            .min_by(|m0, m1| {
-                m0.distance_squared(attack_goal)
-                    .cmp(&m1.distance_squared(attack_goal))
+            .min_by_key(|m| {
+                let steps = dijkstra_map
+                    .distance(&DungeonPosition::from_vec2(m.as_vec2()))
+                    .unwrap_or(DijkstraDistance::MAX);
+
+                (steps, m.distance_squared(attack_goal))
             })
             .copied()
     }
@@ -118,6 +171,28 @@ impl MonsterActionDeterminer {
         )
     }
 
+    /// Step uphill on the threatening player's [`DijkstraMap`] (i.e. away from
+    /// the player), falling back to the straight-line distance.
+    fn flee(
+        &self,
+        threat: IVec2,
+        dijkstra_maps: &DijkstraMaps,
+        valid_moves: &[IVec2],
+    ) -> Option<IVec2> {
+        let dijkstra_map = dijkstra_maps.get(&threat);
+
+        valid_moves
+            .iter()
+            .min_by_key(|m| {
+                let steps = dijkstra_map
+                    .and_then(|d| d.distance(&DungeonPosition::from_vec2(m.as_vec2())))
+                    .unwrap_or(0);
+
+                Reverse((steps, m.distance_squared(threat)))
+            })
+            .copied()
+    }
+
     fn gather_valid_moves(
         &self,
         monster_positions: &MonsterPositionSet,
@@ -131,6 +206,31 @@ impl MonsterActionDeterminer {
             .collect()
     }
 
+    /// Move when there is no player to chase, based on the current behavior.
+    fn idle_move(
+        &self,
+        dijkstra_maps: &DijkstraMaps,
+        dungeon: &DungeonMap,
+        rng: &mut RandomGenerator,
+        valid_moves: &[IVec2],
+    ) -> Option<IVec2> {
+        let destination = match self.behavior.current {
+            BehaviorKind::Guard => Some(self.behavior.post),
+            BehaviorKind::Patrol => self.behavior.waypoint(),
+            _ => return self.random_move(rng, valid_moves),
+        }
+        .filter(|&destination| destination != self.current_pos)?;
+
+        rng.gen_bool(config::MONSTER_PATROL_MOVE_CHANCE)
+            .then(|| self.chase(destination, dijkstra_maps, dungeon, valid_moves))
+            .flatten()
+    }
+
+    /// A monster with half (or less) of its health left is weak enough to flee.
+    fn is_weak(&self) -> bool {
+        self.health.current * 2 <= self.health.max
+    }
+
     fn move_monster(&self, rng_counter: RandomCounter) -> MonsterAction {
         MonsterAction::Move(MonsterMovesEvent::new(
             self.monster,
@@ -140,6 +240,14 @@ impl MonsterActionDeterminer {
         ))
     }
 
+    fn player_near(&self, players: &PlayerPositionMap) -> bool {
+        let radius = config::MONSTER_WAKE_RADIUS;
+
+        players
+            .keys()
+            .any(|p| p.distance_squared(self.current_pos) <= radius * radius)
+    }
+
     fn random_move(&self, rng: &mut RandomGenerator, valid_moves: &[IVec2]) -> Option<IVec2> {
         if rng.gen_bool(config::MONSTER_MOVE_CHANCE) {
             valid_moves
@@ -150,6 +258,83 @@ impl MonsterActionDeterminer {
         }
     }
 
+    /// Step along the shortest path to a goal that is not a player (e.g. where
+    /// the target was last seen, a post or a waypoint) found by the
+    /// [`AStarPathFinder`]. Falls back to the straight-line distance if the goal
+    /// is unreachable or another monster is in the way.
+    fn step_towards(
+        &self,
+        goal: IVec2,
+        dungeon: &DungeonMap,
+        valid_moves: &[IVec2],
+    ) -> Option<IVec2> {
+        let step = AStarPathFinder::find(
+            DungeonPosition::from_vec2(self.current_pos.as_vec2()),
+            DungeonPosition::from_vec2(goal.as_vec2()),
+            dungeon,
+        )
+        .first_step()
+        .map(|pos| pos.to_vec2().as_ivec2());
+
+        valid_moves
+            .iter()
+            .min_by_key(|&&m| (Some(m) != step, m.distance_squared(goal)))
+            .copied()
+    }
+
+    /// Transition the behavior state machine. Random transitions use the
+    /// seeded [`RandomGenerator`] so they are the same on all p2p clients.
+    fn update_behavior(
+        &mut self,
+        visible_target: Option<IVec2>,
+        players: &PlayerPositionMap,
+        rng: &mut RandomGenerator,
+    ) {
+        use BehaviorKind::*;
+
+        let current = self.behavior.current;
+        let next = match current {
+            Flee if !self.is_weak() => self.behavior.default,
+            Flee => Flee,
+            _ if self.is_weak() && rng.gen_bool(config::MONSTER_FLEE_CHANCE) => Flee,
+            Rest if self.player_near(players) && rng.gen_bool(config::MONSTER_WAKE_CHANCE) => {
+                Wander
+            }
+            Wander
+                if self.behavior.default == Rest
+                    && visible_target.is_none()
+                    && self.memory.is_none()
+                    && rng.gen_bool(config::MONSTER_SLEEP_CHANCE) =>
+            {
+                Rest
+            }
+            Patrol
+                if self.behavior.waypoint() == Some(self.current_pos)
+                    || rng.gen_bool(config::MONSTER_PATROL_GIVE_UP_CHANCE) =>
+            {
+                self.behavior.advance_waypoint();
+                self.behavior_changed = true;
+                Patrol
+            }
+            _ => current,
+        };
+
+        if next != current {
+            self.behavior.current = next;
+            self.behavior_changed = true;
+        }
+    }
+
+    /// Remember where the target was seen, or forget once the remembered
+    /// position is reached without seeing the target.
+    fn update_memory(&self, visible_target: Option<IVec2>) -> MemoryUpdate {
+        match (visible_target, self.memory) {
+            (Some(target), _) => MemoryUpdate::Remember(target),
+            (None, Some(memory)) if memory == self.target_pos => MemoryUpdate::Forget,
+            _ => MemoryUpdate::Keep,
+        }
+    }
+
     fn try_attack(&self, players: &PlayerPositionMap) -> Option<IVec2> {
         players
             .keys()
@@ -161,3 +346,122 @@ impl MonsterActionDeterminer {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dungeon::{MapSize, TileType};
+
+    /// Actions allowed to reach the goal, which is 7 steps away. Patrolling
+    /// monsters only take a step on some of their actions.
+    const MAX_ACTIONS: usize = 1000;
+
+    // the monster has to go around the wall to reach the goal
+    const LAYOUT: &str = "
+        ########
+        #...#..#
+        #.M.#!.#
+        #...#..#
+        #......#
+        ########
+    ";
+
+    #[test]
+    fn chase_remembered_position_around_wall() {
+        let fixture = Fixture::new(LAYOUT);
+        let behavior = Behavior::new(BehaviorKind::Wander, IVec2::ZERO, vec![]);
+        let determiner = fixture.determiner(behavior, Some(fixture.goal));
+
+        let reached = fixture.walk(determiner);
+
+        assert!(reached, "Monster never reached the remembered position");
+    }
+
+    #[test]
+    fn patrol_to_waypoint_around_wall() {
+        let fixture = Fixture::new(LAYOUT);
+        let behavior = Behavior::new(BehaviorKind::Patrol, fixture.monster, vec![fixture.goal]);
+        let determiner = fixture.determiner(behavior, None);
+
+        let reached = fixture.walk(determiner);
+
+        assert!(reached, "Monster never reached the waypoint");
+    }
+
+    struct Fixture {
+        dungeon: DungeonMap,
+        goal: IVec2,
+        monster: IVec2,
+        walls: WallPositionSet,
+    }
+
+    impl Fixture {
+        /// Carve the layout into a dungeon of walls, with its top left corner at
+        /// the origin. M marks the monster's position and ! the goal.
+        fn new(layout: &str) -> Self {
+            let mut fixture = Self {
+                dungeon: DungeonMap::new(MapSize::default()),
+                goal: IVec2::ZERO,
+                monster: IVec2::ZERO,
+                walls: WallPositionSet::new(),
+            };
+
+            let rows = layout.lines().map(str::trim).filter(|row| !row.is_empty());
+            for (y, row) in rows.enumerate() {
+                for (x, tile) in row.chars().enumerate() {
+                    let pos = IVec2::new(x as i32, -(y as i32));
+                    match tile {
+                        '#' => {
+                            fixture.walls.insert(pos);
+                            continue;
+                        }
+                        'M' => fixture.monster = pos,
+                        '!' => fixture.goal = pos,
+                        _ => (),
+                    }
+
+                    let dungeon_pos = DungeonPosition::from_vec2(pos.as_vec2());
+                    fixture.dungeon.set_tile_type(&dungeon_pos, TileType::Floor);
+                }
+            }
+
+            fixture
+        }
+
+        fn determiner(&self, behavior: Behavior, memory: Option<IVec2>) -> MonsterActionDeterminer {
+            MonsterActionDeterminer {
+                behavior,
+                behavior_changed: false,
+                current_pos: self.monster,
+                damage: 1,
+                fov: HashSet::new(),
+                health: Health::new(10),
+                is_throttled: false,
+                memory,
+                memory_update: MemoryUpdate::Keep,
+                monster: Entity::PLACEHOLDER,
+                target_pos: self.monster,
+            }
+        }
+
+        /// Let the monster act (with no player around) until it reaches the goal.
+        fn walk(&self, mut determiner: MonsterActionDeterminer) -> bool {
+            let params = MonsterActionParams {
+                dijkstra_maps: DijkstraMaps::new(),
+                dungeon: &self.dungeon,
+                monsters: MonsterPositionSet::new(),
+                players: PlayerPositionMap::new(),
+                walls: self.walls.clone(),
+            };
+            let mut rng = RandomGenerator::new();
+
+            (0..MAX_ACTIONS).any(|_| {
+                if let Some(MonsterAction::Move(_)) = determiner.determine(&params, &mut rng) {
+                    determiner.current_pos = determiner.target_pos;
+                }
+
+                determiner.current_pos == self.goal
+            })
+        }
+    }
+}
@@ -1,36 +1,55 @@
-use super::{LastAction, Monster};
-use crate::{dungeon::WallTile, player::PlayerId, prelude::*};
+use super::{Behavior, LastAction, Monster, TargetMemory};
+use crate::{
+    dungeon::{DijkstraMap, WallTile},
+    player::PlayerId,
+    prelude::*,
+};
 use bevy::utils::hashbrown::{HashMap, HashSet};
 
-pub type MonsterQuery<'w, 's, 'd, 'f, 'a, 't> = Query<
+pub type MonsterQuery<'w, 's, 'b, 'd, 'f, 'h, 'a, 'm, 't> =
+    Query<'w, 's, MonsterQueryItem<'b, 'd, 'f, 'h, 'a, 'm, 't>, With<Monster>>;
+pub type MonsterQueryItem<'b, 'd, 'f, 'h, 'a, 'm, 't> = (
+    &'b Behavior,
+    &'d Damage,
+    &'f FieldOfView,
+    &'h Health,
+    &'a LastAction,
+    Entity,
+    Option<&'m TargetMemory>,
+    &'t Transform,
+);
+pub type PlayersQuery<'w, 's, 't, 'p, 'd> = Query<
     'w,
     's,
-    (
-        &'d Damage,
-        &'f FieldOfView,
-        &'a LastAction,
-        Entity,
-        &'t Transform,
-    ),
-    With<Monster>,
+    (&'t Transform, Entity, &'p Player, &'d DijkstraMap),
+    (With<Player>, Without<Monster>),
 >;
-pub type PlayersQuery<'w, 's, 't, 'p> =
-    Query<'w, 's, (&'t Transform, Entity, &'p Player), (With<Player>, Without<Monster>)>;
 pub type WallQuery<'w, 's, 't> = Query<'w, 's, &'t Transform, (With<WallTile>, Without<Monster>)>;
 
+/// The [`DijkstraMap`] leading to each player, keyed by player position
+pub type DijkstraMaps<'a> = HashMap<IVec2, &'a DijkstraMap>;
 pub type MonsterPositionSet = HashSet<IVec2>;
 pub type PlayerPositionMap = HashMap<IVec2, (Entity, PlayerId)>;
 pub type WallPositionSet = HashSet<IVec2>;
 
-pub struct MonsterActionParams {
+pub struct MonsterActionParams<'a> {
+    pub dijkstra_maps: DijkstraMaps<'a>,
+    pub dungeon: &'a DungeonMap,
     pub monsters: MonsterPositionSet,
     pub players: PlayerPositionMap,
     pub walls: WallPositionSet,
 }
 
-impl MonsterActionParams {
-    pub fn new(monsters: &MonsterQuery, players: &PlayersQuery, walls: &WallQuery) -> Self {
+impl<'a> MonsterActionParams<'a> {
+    pub fn new(
+        dungeon: &'a DungeonMap,
+        monsters: &MonsterQuery,
+        players: &'a PlayersQuery,
+        walls: &WallQuery,
+    ) -> Self {
         Self {
+            dijkstra_maps: Self::create_dijkstra_maps(players),
+            dungeon,
             monsters: Self::create_current_monster_positions_set(monsters),
             players: Self::create_player_set(players),
             walls: Self::create_wall_set(walls),
@@ -42,6 +61,14 @@ impl MonsterActionParams {
         self.monsters.insert(new_pos);
     }
 
+    fn create_dijkstra_maps(players: &'a PlayersQuery) -> DijkstraMaps<'a> {
+        DijkstraMaps::from_iter(
+            players
+                .iter()
+                .map(|(p, .., dijkstra_map)| (p.translation.truncate().as_ivec2(), dijkstra_map)),
+        )
+    }
+
     fn create_current_monster_positions_set(monsters: &MonsterQuery) -> MonsterPositionSet {
         MonsterPositionSet::from_iter(
             monsters
@@ -51,7 +78,7 @@ impl MonsterActionParams {
     }
 
     fn create_player_set(players: &PlayersQuery) -> PlayerPositionMap {
-        PlayerPositionMap::from_iter(players.iter().map(|(p, player_entity, player)| {
+        PlayerPositionMap::from_iter(players.iter().map(|(p, player_entity, player, _)| {
             (
                 p.translation.truncate().as_ivec2(),
                 (player_entity, player.id),
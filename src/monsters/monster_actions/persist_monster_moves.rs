@@ -1,5 +1,5 @@
 use crate::{
-    common::{DesyncEvent, SnapshotStateEvent},
+    common::SnapshotStateEvent,
     monsters::{MonsterMove, MonsterMoveTracker},
 };
 use bevy::prelude::*;
@@ -7,20 +7,18 @@ use bevy_ggrs::{LocalPlayers, RollbackFrameCount};
 use std::{fs::OpenOptions, io::Write, path::Path};
 
 /// Save monster moves to a file. (Won't work on WASM).
-/// Three reasons why a save can take place:
+/// Two reasons why a save can take place:
 /// 1. Requested by a player pressing and releasing P (SnapshotStateEvent)
-/// 2. A desync event was written by [`super::handle_ggrs_events`]
-/// 3. Autosave is enabled and reached its threshold
+/// 2. Autosave is enabled and reached its threshold
+///
+/// Desyncs are covered by the desync report instead.
 pub fn persist_monster_moves(
-    mut desync_event: EventReader<DesyncEvent>,
     mut monster_tracker: ResMut<MonsterMoveTracker>,
     mut snapshot_event: EventReader<SnapshotStateEvent>,
     frame: Res<RollbackFrameCount>,
     local_player: Res<LocalPlayers>,
 ) {
-    let Some(reason) =
-        snapshot_reason(&mut desync_event, &mut snapshot_event, &mut monster_tracker)
-    else {
+    let Some(reason) = snapshot_reason(&mut snapshot_event, &mut monster_tracker) else {
         return;
     };
 
@@ -57,26 +55,14 @@ pub fn persist_monster_moves(
 
     info!("Saved {} monster moves to {}", moves.len(), file_name);
     monster_tracker.moves.clear();
-
-    if matches!(reason, SnapshotReason::DesyncEvent) {
-        file.flush().unwrap_or_else(|e| error!("{e}"));
-        panic!("Aborting");
-    }
 }
 
 fn snapshot_reason(
-    desync_event: &mut EventReader<DesyncEvent>,
     snapshot_event: &mut EventReader<SnapshotStateEvent>,
     monster_tracker: &mut MonsterMoveTracker,
 ) -> Option<SnapshotReason> {
     if monster_tracker.threshold() {
         Some(SnapshotReason::CountThreshold)
-    } else if let Some(event) = desync_event.read().next() {
-        info!(
-            "Snapshot requested due to Desync event from frame {}",
-            event.frame
-        );
-        Some(SnapshotReason::DesyncEvent)
     } else if let Some(event) = snapshot_event.read().next() {
         info!("Snapshot requested by player {}", event.player_id);
         Some(SnapshotReason::Requested)
@@ -88,6 +74,5 @@ fn snapshot_reason(
 #[derive(Debug)]
 enum SnapshotReason {
     CountThreshold,
-    DesyncEvent,
     Requested,
 }
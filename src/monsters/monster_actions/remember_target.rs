@@ -0,0 +1,34 @@
+use super::{MemoryUpdate, Monster, MonsterActedEvent, TargetMemory};
+use bevy::prelude::*;
+
+/// Update what each monster that acted remembers about its target.
+pub fn remember_target(mut acted_events: EventReader<MonsterActedEvent>, mut commands: Commands) {
+    for event in acted_events.read() {
+        match event.memory {
+            MemoryUpdate::Forget => {
+                commands.entity(event.monster).remove::<TargetMemory>();
+            }
+            MemoryUpdate::Keep => (),
+            MemoryUpdate::Remember(pos) => {
+                commands
+                    .entity(event.monster)
+                    .insert(TargetMemory::new(pos));
+            }
+        }
+    }
+}
+
+/// Tick the target memory timers and forget the target once the timer finishes.
+pub fn tick_target_memory(
+    mut commands: Commands,
+    mut memories: Query<(Entity, &mut TargetMemory), With<Monster>>,
+    time: Res<Time>,
+) {
+    for (monster, mut memory) in &mut memories {
+        memory.tick(time.delta());
+
+        if memory.just_finished() {
+            commands.entity(monster).remove::<TargetMemory>();
+        }
+    }
+}
@@ -1,13 +1,13 @@
 use super::{
-    components::{LastAction, Monster},
+    components::{Behavior, LastAction, Monster, TargetMemory},
+    despawn_monsters::despawn_monsters,
     events::MonstersEventsPlugin,
     monster_actions::*,
     spawn_monsters::spawn_monsters,
 };
 use crate::{
     common,
-    config::{game_mode, GameMode},
-    dungeon::DungeonCoreSet,
+    dungeon::{DungeonCoreSet, NextLevelEvent},
     fov::FovCoreSet,
     hud::HudCoreSet,
     player::SpawnPlayersSet,
@@ -34,10 +34,13 @@ impl Plugin for MonstersPlugin {
         );
 
         let core_systems = (
+            tick_target_memory,
             do_monsters_action,
             attack_player,
             move_monster,
             update_last_action,
+            remember_target,
+            change_behavior,
         )
             .in_set(MonstersCoreSet)
             .chain()
@@ -48,13 +51,25 @@ impl Plugin for MonstersPlugin {
 
         common::add_core_systems(app, core_systems);
 
-        if !game_mode(GameMode::SinglePlayer) {
-            app.rollback_component_with_copy::<LastAction>()
-                .rollback_component_with_copy::<Monster>()
-                .checksum_component_with_hash::<Monster>();
+        common::add_core_systems(
+            app,
+            (despawn_monsters, spawn_monsters)
+                .chain()
+                .run_if(on_event::<NextLevelEvent>)
+                .in_set(SpawnMonstersSet)
+                .after(SpawnPlayersSet)
+                .before(MonstersCoreSet),
+        );
+
+        app.rollback_component_with_clone::<Behavior>()
+            .checksum_component_with_hash::<Behavior>()
+            .rollback_component_with_copy::<LastAction>()
+            .rollback_component_with_copy::<Monster>()
+            .checksum_component_with_hash::<Monster>()
+            .rollback_component_with_clone::<TargetMemory>()
+            .checksum_component_with_hash::<TargetMemory>();
 
-            app.add_systems(GgrsSchedule, persist_monster_moves.after(move_monster));
-        }
+        app.add_systems(GgrsSchedule, persist_monster_moves.after(move_monster));
 
         app.add_plugins(MonstersEventsPlugin);
     }
@@ -1,6 +1,7 @@
 use super::{MonsterBundle, MonsterTemplate};
 use crate::{
-    common::{DungeonAssets, DungeonData},
+    common::{DungeonAssets, DungeonData, DungeonTheme, SpriteAssets},
+    dungeon::DungeonPosition,
     prelude::*,
 };
 use bevy::utils::hashbrown::HashMap;
@@ -13,8 +14,11 @@ pub fn spawn_monsters(
     dungeon_data_assets: Res<Assets<DungeonData>>,
     mut commands: Commands,
     mut rng: ResMut<RandomGenerator>,
+    sprite_assets: Res<SpriteAssets>,
+    theme: Res<DungeonTheme>,
 ) {
-    let monster_distribution = create_distribution(dungeon_data_assets.get(&dungeon_assets.data));
+    let monster_distribution =
+        create_distribution(dungeon_data_assets.get(&dungeon_assets.data), &theme);
 
     let stats = dungeon
         .monster_starting_positions
@@ -25,13 +29,17 @@ pub fn spawn_monsters(
                 pos.to_vec2(),
             )
         })
-        .map(|(template, pos)| MonsterBundle::new(template, pos))
+        .map(|(template, pos)| {
+            MonsterBundle::new(template, pos, patrol_route(pos, &dungeon), &sprite_assets)
+        })
         .fold(HashMap::new(), |mut acc, monster_bundle| {
             acc.entry(monster_bundle.monster.name().to_string())
                 .and_modify(|count| *count += 1)
                 .or_insert(1);
 
-            commands.spawn(monster_bundle).add_rollback();
+            commands
+                .spawn((monster_bundle, InGameEntity))
+                .add_rollback();
 
             acc
         });
@@ -39,13 +47,55 @@ pub fn spawn_monsters(
     info!("Spawned monsters: {stats:?}");
 }
 
+/// Waypoints at the corners of a square around the given starting position.
+/// Each is the floor tile within the square that is closest to its corner, so
+/// the monster can usually walk the loop.
+fn patrol_route(pos: Vec2, dungeon: &DungeonMap) -> Vec<IVec2> {
+    let origin = pos.as_ivec2();
+    let r = config::MONSTER_PATROL_RADIUS;
+    let floor_tiles: Vec<IVec2> = (-r..=r)
+        .flat_map(|y| (-r..=r).map(move |x| origin + IVec2::new(x, y)))
+        .filter(|p| dungeon.is_walkable(&DungeonPosition::from_vec2(p.as_vec2())))
+        .collect();
+
+    let mut waypoints: Vec<IVec2> = [
+        IVec2::new(r, r),
+        IVec2::new(-r, r),
+        IVec2::new(-r, -r),
+        IVec2::new(r, -r),
+    ]
+    .into_iter()
+    .filter_map(|corner| {
+        floor_tiles
+            .iter()
+            .min_by_key(|p| p.distance_squared(origin + corner))
+            .copied()
+    })
+    .collect();
+    waypoints.dedup();
+
+    waypoints
+}
+
 /// Create a distribution of monster templates based on their frequency so that
 /// those with a higher frequency are more likely to be randomly selected.
-fn create_distribution(dungeon_data: Option<&DungeonData>) -> Vec<&MonsterTemplate> {
-    dungeon_data
+/// Only monsters allowed by the level's theme are included.
+fn create_distribution<'a>(
+    dungeon_data: Option<&'a DungeonData>,
+    theme: &DungeonTheme,
+) -> Vec<&'a MonsterTemplate> {
+    let distribution: Vec<&MonsterTemplate> = dungeon_data
         .expect("Failed to load dungeon data")
         .monsters
         .iter()
+        .filter(|template| theme.allows_monster(template.monster))
         .flat_map(|template| repeat(template).take(template.frequency))
-        .collect()
+        .collect();
+    assert!(
+        !distribution.is_empty(),
+        "No monsters for theme {}",
+        theme.name
+    );
+
+    distribution
 }
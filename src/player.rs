@@ -14,6 +14,7 @@ mod local_player;
 mod player_actions {
     mod attack_monster;
     mod do_player_action;
+    mod drop_disconnected_players;
     mod grab_item;
     mod handle_move_intent;
     mod item_grabber;
@@ -28,10 +29,11 @@ mod player_actions {
 
     pub(super) use attack_monster::attack_monster;
     pub(super) use do_player_action::do_player_action;
+    pub(super) use drop_disconnected_players::drop_disconnected_players;
     pub(super) use grab_item::grab_item;
     pub(super) use handle_move_intent::handle_move_intent;
     pub(super) use move_player::move_player;
-    pub(super) use player_action::PlayerAction;
+    pub use player_action::{MoveDirection, PlayerAction};
     pub(super) use read_player_inputs::read_player_inputs;
     pub(super) use stop_moving::stop_moving;
     pub(super) use tick_move_throttle::tick_move_throttle;
@@ -43,12 +45,16 @@ mod player_actions {
     use item_user::*;
     use move_intent_handler::*;
 }
+mod place_players;
 mod plugin;
 mod spawn_players;
 
-pub use components::{Inventory, MoveThrottle, Obstacle, Player, PlayerCamera, PlayerId};
+pub use components::{
+    Inventory, MoveThrottle, Obstacle, Player, PlayerCamera, PlayerId, PlayerStats,
+};
 pub use events::{InventoryUpdatedEvent, PlayerMovesEvent, WeaponWieldedEvent};
 pub use local_player::{LocalPlayer, PlayersQuery};
+pub use player_actions::{MoveDirection, PlayerAction};
 pub use plugin::{PlayerCoreSet, PlayerPlugin, SpawnPlayersSet};
 
 // use crate::config::*;
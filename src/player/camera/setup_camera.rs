@@ -6,6 +6,7 @@ pub fn setup_camera(mut commands: Commands) {
     commands.spawn((
         PlayerCamera,
         Camera2d,
+        InGameEntity,
         Camera {
             order: 0,
             ..default()
@@ -5,7 +5,7 @@ pub use move_throttle::MoveThrottle;
 use crate::items::MagicItem;
 use bevy::prelude::Component;
 
-#[derive(Component, Clone, Hash)]
+#[derive(Component, Clone, Debug, Hash)]
 pub struct Inventory {
     pub items: Vec<MagicItem>,
 }
@@ -17,7 +17,7 @@ impl Inventory {
 }
 
 /// An entity that can interfere with a player's intended movement
-#[derive(Component, Clone, Copy, Hash)]
+#[derive(Component, Clone, Copy, Debug, Hash)]
 pub enum Obstacle {
     Monster,
     Player,
@@ -33,3 +33,11 @@ pub struct Player {
 }
 
 pub type PlayerId = usize;
+
+/// Tallies reported on the game over screen
+#[derive(Clone, Component, Copy, Debug, Default, Hash)]
+pub struct PlayerStats {
+    pub monsters_killed: usize,
+    /// Moves made by the player, i.e. the turns survived
+    pub turns: usize,
+}
@@ -21,22 +21,17 @@ impl LocalPlayer {
     }
 
     pub fn is_local(player: &Player, local_players: &LocalPlayers) -> bool {
-        if game_mode(GameMode::MultiPlayer) {
-            local_players.0.contains(&player.id)
-        } else {
-            player.id == 0
-        }
+        Self::is_local_player_id(player.id, local_players)
     }
 
     pub fn is_local_player_id(id: PlayerId, local_players: &LocalPlayers) -> bool {
         id == Self::local_player_id(local_players)
     }
 
+    /// In P2P mode, GGRS lists our one local player. There are no local players
+    /// without a session (single player mode) and all players are local in a
+    /// sync test session, so we default to (or pick) the first player.
     pub fn local_player_id(local_players: &LocalPlayers) -> PlayerId {
-        if game_mode(GameMode::MultiPlayer) {
-            local_players.0.get(0).copied().unwrap_or_default()
-        } else {
-            0
-        }
+        local_players.0.get(0).copied().unwrap_or_default()
     }
 }
@@ -0,0 +1,26 @@
+use super::components::Player;
+use crate::{fov::RecalculateFovEvent, prelude::*};
+
+/// Move the players to their starting positions on a newly spawned dungeon
+/// level. Their FOV refers to the tiles of the prior level, so it is reset and
+/// recalculated. Health, inventory and weapon carry over.
+pub fn place_players(
+    dungeon: Res<DungeonMap>,
+    mut players: Query<(Entity, &Player, &mut FieldOfView, &mut Transform)>,
+    mut recalculate_fov: EventWriter<RecalculateFovEvent>,
+) {
+    for (entity, player, mut fov, mut transform) in &mut players {
+        let player_pos = dungeon.player_starting_positions[player.id];
+        transform.translation = player_pos.to_vec3(config::PLAYER_Z_LAYER);
+        fov.visible_tiles.clear();
+
+        info!(
+            "Placed player {} at {player_pos} on level {}",
+            player.id, dungeon.level
+        );
+        recalculate_fov.send(RecalculateFovEvent::new(
+            entity,
+            player_pos.to_vec2().as_ivec2(),
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use super::PlayerAttacksEvent;
+use super::{Player, PlayerAttacksEvent, PlayerStats};
 use crate::{
     health::{Healing, Health, HealthUnit},
     monsters::Monster,
@@ -9,6 +9,7 @@ pub fn attack_monster(
     mut commands: Commands,
     mut event_reader: EventReader<PlayerAttacksEvent>,
     mut monsters: Query<&mut Health, With<Monster>>,
+    mut players: Query<(&Player, &mut PlayerStats)>,
 ) {
     for event in event_reader.read() {
         let mut health = monsters.get_mut(event.monster).expect("Inconceivable!");
@@ -17,6 +18,11 @@ pub fn attack_monster(
 
         if event.damage >= health.current {
             commands.entity(event.monster).despawn_recursive();
+            let (_, mut stats) = players
+                .iter_mut()
+                .find(|(player, _)| player.id == event.player_id)
+                .expect("Inconceivable!");
+            stats.monsters_killed += 1;
         } else {
             health.current -= event.damage;
             commands.entity(event.monster).insert(Healing::default());
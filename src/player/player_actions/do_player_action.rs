@@ -1,7 +1,7 @@
 use super::*;
 use crate::{
     common::SnapshotStateEvent,
-    config::{self, GameMode},
+    config::{self, NumPlayers},
     dungeon::{RevealDungeonEvent, ZoomEvent},
 };
 use bevy::prelude::*;
@@ -23,10 +23,15 @@ pub fn do_player_action(
     mut zoom_event: EventWriter<ZoomEvent>,
     mut keys: ResMut<ButtonInput<KeyCode>>,
     ggrs_inputs: Option<Res<PlayerInputs<config::GgrsSessionConfig>>>,
+    num_players: Res<NumPlayers>,
     picked_items: PickedItemQuery,
     players: Query<(Entity, &Player)>,
 ) {
-    assert_player_count(players.iter().count());
+    // disconnected players are dropped from the game
+    assert!(
+        players.iter().count() <= num_players.0,
+        "Unexpected player count!"
+    );
 
     for (player_entity, player) in &players {
         let action = if let Some(ggrs_inputs) = ggrs_inputs.as_ref() {
@@ -46,7 +51,9 @@ pub fn do_player_action(
                     dir.to_ivec2(),
                 ));
             }
-            PlayerAction::None => (),
+            // handled by toggle_pause and restart_game, which also run while
+            // the game is paused or over
+            PlayerAction::None | PlayerAction::Pause | PlayerAction::Restart => (),
             PlayerAction::RevealDungeonCheat => {
                 reveal_event.send(RevealDungeonEvent::new(player.id).cheat());
             }
@@ -68,13 +75,3 @@ pub fn do_player_action(
         };
     }
 }
-
-fn assert_player_count(count: usize) {
-    let expected_player_count = match config::GAME_MODE {
-        GameMode::GgrsSyncTest => config::NUM_PLAYERS,
-        GameMode::MultiPlayer => config::NUM_PLAYERS,
-        GameMode::SinglePlayer => 1,
-    };
-
-    assert_eq!(count, expected_player_count, "Unexpected player count!");
-}
@@ -0,0 +1,25 @@
+use super::*;
+use crate::config;
+use bevy::prelude::*;
+use bevy_ggrs::{ggrs::InputStatus, PlayerInputs};
+
+/// Remove the players GGRS has disconnected from the game. GGRS agrees on the
+/// disconnect frame among the remaining peers, so all of them drop the player
+/// on the same frame. Also applies to players respawned by a restart.
+pub fn drop_disconnected_players(
+    mut commands: Commands,
+    ggrs_inputs: Option<Res<PlayerInputs<config::GgrsSessionConfig>>>,
+    players: Query<(Entity, &Player)>,
+) {
+    let Some(ggrs_inputs) = ggrs_inputs else {
+        return;
+    };
+
+    players
+        .iter()
+        .filter(|(_, player)| ggrs_inputs[player.id].1 == InputStatus::Disconnected)
+        .for_each(|(entity, player)| {
+            info!("Dropping disconnected player {}", player.id);
+            commands.entity(entity).despawn_recursive();
+        });
+}
@@ -2,6 +2,7 @@ use super::{
     item_grabber::EquipEvent, GrabItemEvent, ItemGrabber, ItemQuery, PlayerInventoryQuery,
 };
 use crate::{
+    common::{Change, PendingChange},
     player::{InventoryUpdatedEvent, WeaponWieldedEvent},
     prelude::*,
 };
@@ -9,10 +10,13 @@ use crate::{
 /// Handle a GrabItemEvent by grabbing the item the player is over (if any),
 /// despawning it, and then inserting it into the player's inventory.
 /// Sends an InventoryUpdatedEvent to update the HUD.
+/// Grabbing the Amulet of Yala requests the win as a [`PendingChange`], since
+/// the grab may be mispredicted and the end of the game is not rolled back.
 pub fn grab_item(
     mut commands: Commands,
     mut grab_events: EventReader<GrabItemEvent>,
     mut inventory_event: EventWriter<InventoryUpdatedEvent>,
+    mut pending: ResMut<PendingChange>,
     mut players: PlayerInventoryQuery,
     mut wield_event: EventWriter<WeaponWieldedEvent>,
     items: ItemQuery,
@@ -32,6 +36,9 @@ pub fn grab_item(
                 EquipEvent::Wield(event) => {
                     wield_event.send(event);
                 }
+                EquipEvent::Win(player_id) => {
+                    pending.request(Change::Win(player_id));
+                }
             }
         });
 }
@@ -59,6 +59,7 @@ impl ItemGrabber {
         commands.entity(item_entity).despawn_recursive();
 
         match item {
+            MagicItem::Amulet => EquipEvent::Win(self.player_id),
             MagicItem::HealingPotion(_) | MagicItem::Map => {
                 let mut inventory = players
                     .get_mut(self.player)
@@ -84,4 +85,5 @@ impl ItemGrabber {
 pub enum EquipEvent {
     InventoryUpdate(InventoryUpdatedEvent),
     Wield(WeaponWieldedEvent),
+    Win(PlayerId),
 }
@@ -1,15 +1,15 @@
-use super::{MoveThrottle, Player, PlayerMovesEvent};
+use super::{MoveThrottle, Player, PlayerMovesEvent, PlayerStats};
 use crate::{config::PLAYER_Z_LAYER, fov::RecalculateFovEvent};
 use bevy::prelude::*;
 
 pub fn move_player(
     mut commands: Commands,
     mut event_reader: EventReader<PlayerMovesEvent>,
-    mut player: Query<&mut Transform, With<Player>>,
+    mut player: Query<(&mut Transform, &mut PlayerStats), With<Player>>,
     mut recalculate_fov: EventWriter<RecalculateFovEvent>,
 ) {
     for event in event_reader.read() {
-        let mut transform = player.get_mut(event.player).expect("Player not found!");
+        let (mut transform, mut stats) = player.get_mut(event.player).expect("Player not found!");
         let old_pos = transform.translation.truncate();
         trace!(
             "Player {} moves from {old_pos} to {}",
@@ -17,6 +17,7 @@ pub fn move_player(
             event.pos
         );
         transform.translation = event.pos.as_vec2().extend(PLAYER_Z_LAYER);
+        stats.turns += 1;
         commands
             .entity(event.player)
             .insert(MoveThrottle::default());
@@ -10,6 +10,10 @@ pub enum PlayerAction {
     Move(MoveDirection),
     #[default]
     None,
+    /// Pause or resume the game for all players
+    Pause,
+    /// Start a new game from the game over (or game won) screen or pause menu
+    Restart,
     /// Show the full dungeon map
     RevealDungeonCheat,
     Snapshot,
@@ -42,6 +46,8 @@ impl From<u8> for PlayerAction {
             4 => PlayerAction::Move(MoveDirection::Right),
             5 => PlayerAction::StopMoving,
             6 => PlayerAction::GrabItem,
+            7 => PlayerAction::Restart,
+            8 => PlayerAction::Pause,
             v if v >= 10 && v <= 18 => PlayerAction::UseItem(v - 10),
             50 => PlayerAction::ZoomIn,
             51 => PlayerAction::ZoomOut,
@@ -89,9 +95,11 @@ impl From<&mut ButtonInput<KeyCode>> for PlayerAction {
             .or_else(|| single_press(keys, Digit7, UseItem(6)))
             .or_else(|| single_press(keys, Digit8, UseItem(7)))
             .or_else(|| single_press(keys, Digit9, UseItem(8)))
+            .or_else(|| single_press(keys, Escape, PlayerAction::Pause))
             .or_else(|| single_press(keys, KeyG, GrabItem))
             .or_else(|| single_press(keys, KeyM, RevealDungeonCheat))
             .or_else(|| single_press(keys, KeyP, Snapshot))
+            .or_else(|| single_press(keys, KeyR, Restart))
             .or_else(|| {
                 keys.any_pressed([ShiftLeft, ShiftRight])
                     .then(|| {
@@ -113,6 +121,8 @@ impl Into<u8> for PlayerAction {
             PlayerAction::Move(MoveDirection::Left) => 3,
             PlayerAction::Move(MoveDirection::Right) => 4,
             PlayerAction::GrabItem => 6,
+            PlayerAction::Restart => 7,
+            PlayerAction::Pause => 8,
             PlayerAction::RevealDungeonCheat => 101,
             PlayerAction::StopMoving => 5,
             PlayerAction::Snapshot => 100,
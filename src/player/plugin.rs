@@ -1,15 +1,16 @@
 use super::{
     camera::{follow_with_camera, setup_camera},
-    components::{Inventory, MoveThrottle, Obstacle, Player},
+    components::{Inventory, MoveThrottle, Obstacle, Player, PlayerStats},
     events::PlayerEventsPlugin,
+    place_players::place_players,
     player_actions::*,
     spawn_players::spawn_players,
 };
 use crate::{
     common,
-    config::{game_mode, GameMode},
-    dungeon::{DungeonCoreSet, SpawnDungeonSet},
+    dungeon::{DungeonCoreSet, NextLevelEvent, SpawnDungeonSet},
     monsters::MonstersCoreSet,
+    replay::ReplayPlayer,
     GameState,
 };
 use bevy::prelude::*;
@@ -33,6 +34,7 @@ impl Plugin for PlayerPlugin {
         );
 
         let core_systems = (
+            drop_disconnected_players,
             do_player_action,
             tick_move_throttle,
             stop_moving,
@@ -50,18 +52,31 @@ impl Plugin for PlayerPlugin {
 
         common::add_core_systems(app, core_systems);
 
-        if !game_mode(GameMode::SinglePlayer) {
-            app.rollback_component_with_clone::<Inventory>()
-                .checksum_component_with_hash::<Inventory>()
-                .rollback_component_with_clone::<MoveThrottle>()
-                .checksum_component_with_hash::<MoveThrottle>()
-                .rollback_component_with_copy::<Player>()
-                .checksum_component_with_hash::<Player>()
-                .rollback_component_with_copy::<Obstacle>()
-                .checksum_component_with_hash::<Obstacle>();
+        common::add_core_systems(
+            app,
+            place_players
+                .run_if(on_event::<NextLevelEvent>)
+                .in_set(SpawnPlayersSet)
+                .after(SpawnDungeonSet)
+                .before(MonstersCoreSet),
+        );
+
+        app.rollback_component_with_clone::<Inventory>()
+            .checksum_component_with_hash::<Inventory>()
+            .rollback_component_with_clone::<MoveThrottle>()
+            .checksum_component_with_hash::<MoveThrottle>()
+            .rollback_component_with_copy::<Player>()
+            .checksum_component_with_hash::<Player>()
+            .rollback_component_with_copy::<Obstacle>()
+            .checksum_component_with_hash::<Obstacle>()
+            .rollback_component_with_copy::<PlayerStats>()
+            .checksum_component_with_hash::<PlayerStats>();
 
-            app.add_systems(ReadInputs, read_player_inputs);
-        }
+        // recorded or scripted inputs replace the keyboard (see feed_replay_inputs)
+        app.add_systems(
+            ReadInputs,
+            read_player_inputs.run_if(not(resource_exists::<ReplayPlayer>)),
+        );
 
         app.add_plugins(PlayerEventsPlugin);
     }
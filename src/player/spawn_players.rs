@@ -1,31 +1,27 @@
-use super::components::{Inventory, Obstacle, Player};
-use crate::{
-    config::{PLAYER_HEIGHT, PLAYER_WIDTH},
-    hud::TooltipLabel,
-    prelude::*,
-};
+use super::components::{Inventory, Obstacle, Player, PlayerStats};
+use crate::{common::SpriteAssets, dungeon::DijkstraMap, hud::TooltipLabel, prelude::*};
 use bevy_ggrs::AddRollbackCommandExtension;
 
-pub fn spawn_players(dungeon: Res<DungeonMap>, mut commands: Commands) {
+pub fn spawn_players(
+    dungeon: Res<DungeonMap>,
+    mut commands: Commands,
+    sprite_assets: Res<SpriteAssets>,
+) {
     for (player_idx, &player_pos) in dungeon.player_starting_positions.iter().enumerate() {
-        let color = match player_idx {
-            0 => config::PLAYER_0_COLOR,
-            _ => config::PLAYER_1_COLOR,
-        };
+        let color = config::PLAYER_COLORS[player_idx];
 
         let id = commands
             .spawn((
                 Player { id: player_idx },
                 Damage(1),
+                DijkstraMap::new(),
                 FieldOfView::new(config::PLAYER_FOV_RADIUS),
                 Health::new(config::PLAYER_HEALTH_MAX),
+                InGameEntity,
                 Inventory::new(),
                 Obstacle::Player,
-                Sprite {
-                    color,
-                    custom_size: Some(Vec2::new(PLAYER_WIDTH, PLAYER_HEIGHT)),
-                    ..default()
-                },
+                PlayerStats::default(),
+                sprite_assets.sprite(Some(config::PLAYER_SPRITE), color),
                 TooltipLabel(format!("Player {}", player_idx)),
                 Transform::from_translation(player_pos.to_vec3(config::PLAYER_Z_LAYER)),
                 Visibility::Hidden,
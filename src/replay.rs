@@ -0,0 +1,8 @@
+mod playback;
+mod plugin;
+mod record;
+mod replay_file;
+
+pub use playback::ReplayPlayer;
+pub use plugin::ReplayPlugin;
+pub use replay_file::Replay;
@@ -0,0 +1,104 @@
+use super::Replay;
+use crate::prelude::*;
+use bevy_ggrs::{LocalInputs, LocalPlayers, Rollback, RollbackFrameCount};
+
+/// The recording being played back and whether to verify its checksums.
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    /// Frame whose checksum did not match the recording, if any
+    pub diverged_at: Option<usize>,
+    pub replay: Replay,
+    pub verify: bool,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay, verify: bool) -> Self {
+        Self {
+            diverged_at: None,
+            replay,
+            verify,
+        }
+    }
+}
+
+/// Feed the recorded inputs of the next frame to the GGRS session in place of
+/// the keyboard. Only the local players' inputs are fed, so peers of a P2P
+/// session (e.g. in the two peer harness) can share one script. Pauses once the
+/// recording runs out.
+pub fn feed_replay_inputs(
+    mut commands: Commands,
+    frame: Res<RollbackFrameCount>,
+    local_players: Res<LocalPlayers>,
+    player: Res<ReplayPlayer>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let next_index = frame.0 as usize;
+    let inputs = player.replay.inputs.get(next_index);
+    if inputs.is_none() {
+        info!("Replay finished after {next_index} frames");
+        time.pause();
+    }
+
+    let local_inputs = local_players
+        .0
+        .iter()
+        .map(|&handle| {
+            let input = inputs.and_then(|i| i.get(handle)).copied().unwrap_or(0);
+            (handle, input)
+        })
+        .collect();
+
+    commands.insert_resource(LocalInputs::<config::GgrsSessionConfig>(local_inputs));
+}
+
+/// Compare the state after each frame with the recorded checksum, pausing on
+/// the first frame that diverges.
+pub fn verify_frame(
+    frame: Res<RollbackFrameCount>,
+    mut player: ResMut<ReplayPlayer>,
+    rng: Res<RandomGenerator>,
+    mut time: ResMut<Time<Virtual>>,
+    transforms: Query<&Transform, With<Rollback>>,
+) {
+    if player.diverged_at.is_some() {
+        return;
+    }
+
+    let frame = frame.0 as usize;
+    let Some(&expected) = player.replay.checksums.get(frame.saturating_sub(1)) else {
+        return;
+    };
+
+    let actual = Replay::checksum(&rng, transforms.iter());
+    if actual != expected {
+        error!("Replay diverged at frame {frame}. Expected checksum {expected:X}, got {actual:X}");
+        player.diverged_at = Some(frame);
+        time.pause();
+    }
+}
+
+/// Change the playback speed with the bracket keys, and pause or resume with
+/// the space bar.
+pub fn control_replay_speed(keys: Res<ButtonInput<KeyCode>>, mut time: ResMut<Time<Virtual>>) {
+    let speed = time.relative_speed();
+
+    if keys.just_pressed(KeyCode::Space) {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    } else if keys.just_pressed(KeyCode::BracketRight) {
+        time.set_relative_speed((speed * 2.).min(config::REPLAY_SPEED_MAX));
+    } else if keys.just_pressed(KeyCode::BracketLeft) {
+        time.set_relative_speed((speed / 2.).max(config::REPLAY_SPEED_MIN));
+    } else {
+        return;
+    }
+
+    info!(
+        "Replay speed {}x{}",
+        time.relative_speed(),
+        if time.is_paused() { " (paused)" } else { "" }
+    );
+}
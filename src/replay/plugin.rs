@@ -0,0 +1,50 @@
+use super::{
+    playback::{control_replay_speed, feed_replay_inputs, verify_frame, ReplayPlayer},
+    record::{record_frame, save_recording, start_recording, ReplayRecorder},
+};
+use crate::prelude::*;
+use bevy_ggrs::{AdvanceWorld, AdvanceWorldSet, ReadInputs};
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(GameState::InGame),
+            start_recording.run_if(not(resource_exists::<ReplayRecorder>)),
+        )
+        .add_systems(
+            AdvanceWorld,
+            record_frame
+                .run_if(resource_exists::<ReplayRecorder>)
+                .in_set(AdvanceWorldSet::Last),
+        )
+        .add_systems(
+            OnEnter(GameState::GameOver),
+            save_recording.run_if(resource_exists::<ReplayRecorder>),
+        )
+        .add_systems(
+            OnEnter(GameState::GameWon),
+            save_recording.run_if(resource_exists::<ReplayRecorder>),
+        )
+        .add_systems(
+            Last,
+            save_recording.run_if(resource_exists::<ReplayRecorder>.and(on_event::<AppExit>)),
+        );
+
+        app.add_systems(
+            ReadInputs,
+            feed_replay_inputs.run_if(resource_exists::<ReplayPlayer>),
+        )
+        .add_systems(
+            AdvanceWorld,
+            verify_frame
+                .run_if(game_mode(GameMode::Replay).and(|player: Res<ReplayPlayer>| player.verify))
+                .in_set(AdvanceWorldSet::Last),
+        )
+        .add_systems(
+            Update,
+            control_replay_speed.run_if(game_mode(GameMode::Replay)),
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use super::Replay;
+use crate::{prelude::*, startup::LaunchOptions};
+use bevy_ggrs::{PlayerInputs, Rollback, RollbackFrameCount, Session};
+use std::path::PathBuf;
+
+/// The game being recorded and the file it is saved to.
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    path: PathBuf,
+    replay: Replay,
+}
+
+/// Start recording when the game starts if a `record` file was given at launch.
+/// Only GGRS sessions advance in fixed time steps, so single player games
+/// cannot be recorded.
+pub fn start_recording(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    launch_options: Res<LaunchOptions>,
+    num_players: Res<NumPlayers>,
+    rng: Res<RandomGenerator>,
+) {
+    let Some(path) = &launch_options.record else {
+        return;
+    };
+
+    match *game_mode {
+        GameMode::GgrsSyncTest | GameMode::MultiPlayer => {
+            info!("Recording game to {}", path.display());
+            commands.insert_resource(ReplayRecorder {
+                path: path.clone(),
+                replay: Replay::new(rng.seed, num_players.0),
+            });
+        }
+        GameMode::Replay => {}
+        GameMode::SinglePlayer => warn!("Single player games cannot be recorded"),
+    }
+}
+
+/// Record the inputs of each frame along with the resulting checksum. Runs
+/// after the GgrsSchedule, including when re-simulating frames on rollback.
+pub fn record_frame(
+    frame: Res<RollbackFrameCount>,
+    ggrs_inputs: Res<PlayerInputs<config::GgrsSessionConfig>>,
+    mut recorder: ResMut<ReplayRecorder>,
+    rng: Res<RandomGenerator>,
+    transforms: Query<&Transform, With<Rollback>>,
+) {
+    let inputs = ggrs_inputs.iter().map(|(input, _)| *input).collect();
+    let checksum = Replay::checksum(&rng, transforms.iter());
+
+    recorder.replay.record(frame.0 as usize, inputs, checksum);
+}
+
+/// Save the recording when the game ends or the app exits. Frames GGRS has not
+/// confirmed may still be rolled back, so they are left out.
+pub fn save_recording(
+    recorder: Res<ReplayRecorder>,
+    session: Option<Res<Session<config::GgrsSessionConfig>>>,
+) {
+    let mut replay = recorder.replay.clone();
+    if let Some(Session::P2P(s)) = session.as_deref() {
+        replay.truncate((s.confirmed_frame() + 1).max(0) as usize);
+    }
+
+    match replay.save(&recorder.path) {
+        Ok(()) => info!(
+            "Saved {} frames to {}",
+            replay.len(),
+            recorder.path.display()
+        ),
+        Err(e) => error!("{e}"),
+    }
+}
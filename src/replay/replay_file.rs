@@ -0,0 +1,141 @@
+use crate::{prelude::*, startup::checksum_transform};
+use bevy::scene::ron;
+use bevy_ggrs::checksum_hasher;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+/// A recorded game: the shared random seed plus the encoded
+/// [`PlayerAction`](crate::player::PlayerAction) of every player for each frame.
+/// Since the game is deterministic, that is all it takes to replay it exactly.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Replay {
+    /// Checksum of the game state after each frame (see [`Replay::checksum`])
+    pub checksums: Vec<u64>,
+    /// Inputs of all players for each frame, starting with frame 1
+    pub inputs: Vec<Vec<u8>>,
+    pub num_players: usize,
+    pub seed: u64,
+}
+
+impl Replay {
+    pub fn new(seed: u64, num_players: usize) -> Self {
+        Self {
+            num_players,
+            seed,
+            ..default()
+        }
+    }
+
+    /// Checksum of the state after a frame: the random generator, which every
+    /// random decision advances, plus the position of every rollback entity.
+    pub fn checksum<'a>(
+        rng: &RandomGenerator,
+        transforms: impl Iterator<Item = &'a Transform>,
+    ) -> u64 {
+        let mut hasher = checksum_hasher();
+        rng.hash(&mut hasher);
+
+        transforms.fold(hasher.finish(), |acc, transform| {
+            acc.wrapping_add(checksum_transform(transform))
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read replay {}: {e}", path.display()))?;
+
+        ron::from_str(&contents).map_err(|e| format!("Invalid replay {}: {e}", path.display()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// Number of frames recorded
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Record the inputs and resulting checksum of a frame. GGRS re-simulates
+    /// frames on rollback, so any frames recorded after it are discarded.
+    pub fn record(&mut self, frame: usize, inputs: Vec<u8>, checksum: u64) {
+        let index = frame.saturating_sub(1).min(self.len());
+        self.truncate(index);
+        self.inputs.push(inputs);
+        self.checksums.push(checksum);
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = ron::to_string(self).map_err(|e| format!("Unable to encode replay: {e}"))?;
+
+        fs::write(path, contents)
+            .map_err(|e| format!("Unable to write replay {}: {e}", path.display()))
+    }
+
+    /// Keep only the first `frames` frames.
+    pub fn truncate(&mut self, frames: usize) {
+        self.inputs.truncate(frames);
+        self.checksums.truncate(frames);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_frames() {
+        let mut replay = Replay::new(42, 2);
+
+        replay.record(1, vec![0, 1], 10);
+        replay.record(2, vec![2, 0], 20);
+
+        assert_eq!(replay.inputs, vec![vec![0, 1], vec![2, 0]]);
+        assert_eq!(replay.checksums, vec![10, 20]);
+    }
+
+    #[test]
+    fn rollback_discards_later_frames() {
+        let mut replay = Replay::new(42, 2);
+        replay.record(1, vec![0, 0], 10);
+        replay.record(2, vec![0, 0], 20);
+        replay.record(3, vec![0, 0], 30);
+
+        replay.record(2, vec![1, 0], 21);
+
+        assert_eq!(replay.inputs, vec![vec![0, 0], vec![1, 0]]);
+        assert_eq!(replay.checksums, vec![10, 21]);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join("dungeon_crawl_save_and_load.replay");
+        let mut replay = Replay::new(42, 2);
+        replay.record(1, vec![7, 101], 10);
+
+        replay.save(&path).expect("save failed");
+        let loaded = Replay::load(&path).expect("load failed");
+
+        assert_eq!(loaded, replay);
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn checksum_depends_on_rng_and_transforms() {
+        let rng = RandomGenerator::from_seed(42);
+        let transforms = [Transform::from_xyz(1., 2., 3.)];
+        let checksum = Replay::checksum(&rng, transforms.iter());
+
+        let mut advanced = rng.clone();
+        advanced.gen_range(0..10);
+        let moved = [Transform::from_xyz(2., 2., 3.)];
+
+        assert_eq!(checksum, Replay::checksum(&rng.clone(), transforms.iter()));
+        assert_ne!(checksum, Replay::checksum(&advanced, transforms.iter()));
+        assert_ne!(checksum, Replay::checksum(&rng, moved.iter()));
+    }
+}
@@ -1,14 +1,29 @@
+mod launch_options;
+mod lobby;
+mod main_menu;
+mod matchbox_room;
 mod plugin;
 mod startup;
 
 mod ggrs {
     mod checksum_transform;
     mod create_p2p_session;
+    mod desync_report;
+    mod desync_snapshots;
     mod handle_ggrs_events;
+    mod peer_connections;
 
-    pub(super) use checksum_transform::checksum_transform;
+    pub use checksum_transform::checksum_transform;
     pub(super) use create_p2p_session::create_p2p_session;
+    pub use desync_report::{DesyncReport, EntitySnapshot, ReportDiff};
+    pub(super) use desync_snapshots::DesyncSnapshots;
     pub(super) use handle_ggrs_events::handle_ggrs_events;
+    pub(super) use peer_connections::respond_to_disconnect;
+    pub use peer_connections::PeerConnections;
 }
 
-pub use plugin::StartupPlugin;
+pub use ggrs::{checksum_transform, DesyncReport, EntitySnapshot, PeerConnections, ReportDiff};
+pub use launch_options::LaunchOptions;
+pub use lobby::RoomCodeInput;
+pub use matchbox_room::MatchboxRoom;
+pub use plugin::{AssetLoadingPlugin, StartupPlugin};
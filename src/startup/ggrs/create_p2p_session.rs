@@ -4,10 +4,12 @@ use bevy_ggrs::{
     Session,
 };
 use bevy_matchbox::{prelude::PeerId, MatchboxSocket};
+use std::time::Duration;
 
 pub fn create_p2p_session(
     mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
+    num_players: Res<NumPlayers>,
     mut socket: ResMut<MatchboxSocket>,
 ) {
     if socket.get_channel(0).is_err() {
@@ -16,12 +18,14 @@ pub fn create_p2p_session(
 
     socket.update_peers();
     let players = socket.players();
-    if players.len() < config::NUM_PLAYERS {
+    if players.len() < num_players.0 {
         return;
     }
 
-    info!("All peers have joined. Starting game!");
-    commands.insert_resource(RandomGenerator::new_for_p2p(&mut socket));
+    info!("All {} peers have joined. Starting game!", num_players.0);
+    let rng = RandomGenerator::new_for_p2p(&mut socket);
+    info!("Random seed {} derived from the peer ids", rng.seed);
+    commands.insert_resource(rng);
     commands.insert_resource(build_session(players, &mut socket));
 
     next_state.set(GameState::InGame);
@@ -32,10 +36,13 @@ fn build_session(
     socket: &mut MatchboxSocket,
 ) -> Session<config::GgrsSessionConfig> {
     let mut session_builder = ggrs::SessionBuilder::<config::GgrsSessionConfig>::new()
-        .with_num_players(config::NUM_PLAYERS)
+        .with_num_players(players.len())
         // .with_max_prediction_window(0) // lockstep mode
+        .with_max_prediction_window(config::GGRS_MAX_PREDICTION)
         .with_desync_detection_mode(DesyncDetection::On { interval: 1 })
-        .with_input_delay(config::GGRS_INPUT_DELAY);
+        .with_input_delay(config::GGRS_INPUT_DELAY)
+        .with_disconnect_notify_delay(Duration::from_secs(config::GGRS_DISCONNECT_NOTIFY_SECONDS))
+        .with_disconnect_timeout(Duration::from_secs(config::GGRS_DISCONNECT_TIMEOUT_SECONDS));
     for (i, player) in players.into_iter().enumerate() {
         session_builder = session_builder
             .add_player(player, i)
@@ -0,0 +1,333 @@
+use crate::{common::RandomCounter, player::PlayerId};
+use bevy::scene::ron;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+/// Everything a peer knows about a desync detected by GGRS: the frame, both
+/// checksums and a snapshot of every rollback entity as of that frame. Each peer
+/// saves its own, and the `desync_diff` tool compares two of them.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct DesyncReport {
+    pub entities: Vec<EntitySnapshot>,
+    pub frame: i32,
+    #[serde(with = "hex")]
+    pub local_checksum: u128,
+    pub player_id: PlayerId,
+    /// Random numbers generated as of the frame, if still in the snapshots
+    #[serde(with = "hex::option")]
+    pub random_counter: Option<RandomCounter>,
+    #[serde(with = "hex")]
+    pub remote_checksum: u128,
+}
+
+/// The checksummed components of a rollback entity, formatted with `Debug`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct EntitySnapshot {
+    /// Component values by component name
+    pub components: BTreeMap<String, String>,
+    /// Spawn order of the entity, which is the same on all peers (unlike the
+    /// entity itself)
+    pub order: u64,
+}
+
+/// The first difference between two reports.
+#[derive(Debug, PartialEq)]
+pub enum ReportDiff {
+    /// The entity is in one report only
+    Entity { in_first: bool, order: u64 },
+    /// The component differs, or is missing (None) in one report. The field
+    /// is given if the values are structs with the same fields.
+    Field {
+        component: String,
+        field: Option<String>,
+        first: Option<String>,
+        order: u64,
+        second: Option<String>,
+    },
+}
+
+impl DesyncReport {
+    /// Compare with another peer's report, entity by entity in spawn order.
+    pub fn diff(&self, other: &Self) -> Option<ReportDiff> {
+        let first = self.entities_by_order();
+        let second = other.entities_by_order();
+        let mut orders = first
+            .keys()
+            .chain(second.keys())
+            .copied()
+            .collect::<Vec<_>>();
+        orders.sort();
+        orders.dedup();
+
+        orders
+            .into_iter()
+            .find_map(|order| match (first.get(&order), second.get(&order)) {
+                (Some(a), Some(b)) => diff_entity(order, a, b),
+                (a, _) => Some(ReportDiff::Entity {
+                    in_first: a.is_some(),
+                    order,
+                }),
+            })
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("desync_player{}_frame{}.ron", self.player_id, self.frame)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read report {}: {e}", path.display()))?;
+
+        ron::from_str(&contents).map_err(|e| format!("Invalid report {}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        fs::write(path, self.to_ron()?)
+            .map_err(|e| format!("Unable to write report {}: {e}", path.display()))
+    }
+
+    pub fn to_ron(&self) -> Result<String, String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| format!("Unable to encode report: {e}"))
+    }
+
+    fn entities_by_order(&self) -> BTreeMap<u64, &EntitySnapshot> {
+        self.entities.iter().map(|e| (e.order, e)).collect()
+    }
+}
+
+impl fmt::Display for ReportDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReportDiff::Entity { in_first, order } => {
+                let report = if *in_first { "first" } else { "second" };
+                write!(f, "Entity {order} is only in the {report} report")
+            }
+            ReportDiff::Field {
+                component,
+                field,
+                first,
+                order,
+                second,
+            } => {
+                let field = field.as_ref().map_or(String::new(), |f| format!(".{f}"));
+                let missing = || "(missing)".to_string();
+                write!(
+                    f,
+                    "Entity {order} {component}{field} differs:\n  first:  {}\n  second: {}",
+                    first.clone().unwrap_or_else(missing),
+                    second.clone().unwrap_or_else(missing)
+                )
+            }
+        }
+    }
+}
+
+fn diff_entity(order: u64, first: &EntitySnapshot, second: &EntitySnapshot) -> Option<ReportDiff> {
+    let mut components = first
+        .components
+        .keys()
+        .chain(second.components.keys())
+        .collect::<Vec<_>>();
+    components.sort();
+    components.dedup();
+
+    components.into_iter().find_map(|component| {
+        let a = first.components.get(component);
+        let b = second.components.get(component);
+        if a == b {
+            return None;
+        }
+
+        let field = a.zip(b).and_then(|(a, b)| diff_fields(a, b));
+        let (field, a, b) = match field {
+            Some((field, a, b)) => (Some(field), Some(a), Some(b)),
+            None => (None, a.cloned(), b.cloned()),
+        };
+
+        Some(ReportDiff::Field {
+            component: component.clone(),
+            field,
+            first: a,
+            order,
+            second: b,
+        })
+    })
+}
+
+/// The first differing field of two `Debug` formatted structs with the same
+/// fields.
+fn diff_fields(first: &str, second: &str) -> Option<(String, String, String)> {
+    let a = debug_fields(first)?;
+    let b = debug_fields(second)?;
+    if a.len() != b.len() || a.iter().zip(&b).any(|((x, _), (y, _))| x != y) {
+        return None;
+    }
+
+    a.into_iter()
+        .zip(b)
+        .find(|((_, x), (_, y))| x != y)
+        .map(|((field, x), (_, y))| (field, x, y))
+}
+
+/// Split a `Debug` formatted struct like `Health { current: 3, max: 10 }` into
+/// its top level fields. None if not formatted as a struct with named fields.
+fn debug_fields(value: &str) -> Option<Vec<(String, String)>> {
+    let inner = value
+        .split_once('{')
+        .and_then(|(_, rest)| rest.trim_end().strip_suffix('}'))?;
+
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut start = 0;
+    for (i, c) in inner.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                parts.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&inner[start..]);
+
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            part.split_once(": ")
+                .map(|(field, value)| (field.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// RON has no 128 bit integers, so checksums and counters are saved as hex.
+mod hex {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u128, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        u128::from_str_radix(&value, 16).map_err(D::Error::custom)
+    }
+
+    pub fn serialize<S: Serializer>(value: &u128, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{value:X}"))
+    }
+
+    pub mod option {
+        use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<u128>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|value| u128::from_str_radix(&value, 16).map_err(D::Error::custom))
+                .transpose()
+        }
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<u128>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            value
+                .map(|value| format!("{value:X}"))
+                .serialize(serializer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::named("Health { current: 3, max: 10 }", Some(vec![("current", "3"), ("max", "10")]))]
+    #[case::nested(
+        "Transform { translation: Vec3(1.0, 2.0, 0.0), scale: Vec3(1.0, 1.0, 1.0) }",
+        Some(vec![("translation", "Vec3(1.0, 2.0, 0.0)"), ("scale", "Vec3(1.0, 1.0, 1.0)")])
+    )]
+    #[case::string("Monster { name: \"Orc, Sr.\" }", Some(vec![("name", "\"Orc, Sr.\"")]))]
+    #[case::tuple("Damage(1)", None)]
+    fn split_debug_fields(#[case] value: &str, #[case] expected: Option<Vec<(&str, &str)>>) {
+        let expected = expected.map(|fields| {
+            fields
+                .into_iter()
+                .map(|(f, v)| (f.to_string(), v.to_string()))
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(debug_fields(value), expected);
+    }
+
+    #[test]
+    fn first_diverging_field() {
+        let first = report(vec![
+            entity(0, "Player", "Player { id: 0 }"),
+            entity(1, "Health", "Health { current: 3, max: 10 }"),
+        ]);
+        let mut second = first.clone();
+        second.entities[1] = entity(1, "Health", "Health { current: 2, max: 10 }");
+
+        assert_eq!(
+            first.diff(&second),
+            Some(ReportDiff::Field {
+                component: "Health".to_string(),
+                field: Some("current".to_string()),
+                first: Some("3".to_string()),
+                order: 1,
+                second: Some("2".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn missing_entity() {
+        let first = report(vec![entity(0, "Damage", "Damage(1)")]);
+        let second = report(vec![]);
+
+        assert_eq!(
+            first.diff(&second),
+            Some(ReportDiff::Entity {
+                in_first: true,
+                order: 0
+            })
+        );
+        assert_eq!(first.diff(&first), None);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = std::env::temp_dir().join("dungeon_crawl_desync_report.ron");
+        let report = report(vec![entity(3, "Damage", "Damage(1)")]);
+
+        report.save(&path).expect("save failed");
+        let loaded = DesyncReport::load(&path).expect("load failed");
+
+        assert_eq!(loaded, report);
+        fs::remove_file(path).ok();
+    }
+
+    fn entity(order: u64, component: &str, value: &str) -> EntitySnapshot {
+        EntitySnapshot {
+            components: BTreeMap::from([(component.to_string(), value.to_string())]),
+            order,
+        }
+    }
+
+    fn report(entities: Vec<EntitySnapshot>) -> DesyncReport {
+        DesyncReport {
+            entities,
+            frame: 42,
+            local_checksum: 1,
+            random_counter: Some(7),
+            remote_checksum: 2,
+            ..Default::default()
+        }
+    }
+}
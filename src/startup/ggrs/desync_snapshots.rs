@@ -0,0 +1,101 @@
+use super::{DesyncReport, EntitySnapshot};
+use crate::{
+    items::{Grabbable, MagicItem, Weapon},
+    monsters::{Behavior, Monster, TargetMemory},
+    player::{Inventory, MoveThrottle, Obstacle, PlayerId, PlayerStats},
+    prelude::*,
+};
+use bevy::ecs::system::SystemParam;
+use bevy_ggrs::{GgrsComponentSnapshots, GgrsResourceSnapshots, RollbackOrdered};
+use std::{collections::BTreeMap, fmt::Debug};
+
+type EntityComponents = BTreeMap<u64, BTreeMap<String, String>>;
+
+/// The GGRS snapshots of the checksummed rollback components and resources,
+/// from which a [`DesyncReport`] is built.
+#[derive(SystemParam)]
+pub struct DesyncSnapshots<'w> {
+    behaviors: Res<'w, GgrsComponentSnapshots<Behavior>>,
+    damages: Res<'w, GgrsComponentSnapshots<Damage>>,
+    grabbables: Res<'w, GgrsComponentSnapshots<Grabbable>>,
+    healths: Res<'w, GgrsComponentSnapshots<Health>>,
+    inventories: Res<'w, GgrsComponentSnapshots<Inventory>>,
+    magic_items: Res<'w, GgrsComponentSnapshots<MagicItem>>,
+    monsters: Res<'w, GgrsComponentSnapshots<Monster>>,
+    move_throttles: Res<'w, GgrsComponentSnapshots<MoveThrottle>>,
+    obstacles: Res<'w, GgrsComponentSnapshots<Obstacle>>,
+    ordered: Res<'w, RollbackOrdered>,
+    player_stats: Res<'w, GgrsComponentSnapshots<PlayerStats>>,
+    players: Res<'w, GgrsComponentSnapshots<Player>>,
+    rng: Res<'w, GgrsResourceSnapshots<RandomGenerator>>,
+    target_memories: Res<'w, GgrsComponentSnapshots<TargetMemory>>,
+    transforms: Res<'w, GgrsComponentSnapshots<Transform>>,
+    weapons: Res<'w, GgrsComponentSnapshots<Weapon>>,
+}
+
+impl DesyncSnapshots<'_> {
+    /// Report the state as of the desync frame. GGRS only keeps a limited
+    /// number of snapshots, so a frame that is too old reports no entities.
+    pub fn report(
+        &self,
+        frame: i32,
+        local_checksum: u128,
+        player_id: PlayerId,
+        remote_checksum: u128,
+    ) -> DesyncReport {
+        let mut entities = EntityComponents::new();
+        self.add(&mut entities, &self.behaviors, frame);
+        self.add(&mut entities, &self.damages, frame);
+        self.add(&mut entities, &self.grabbables, frame);
+        self.add(&mut entities, &self.healths, frame);
+        self.add(&mut entities, &self.inventories, frame);
+        self.add(&mut entities, &self.magic_items, frame);
+        self.add(&mut entities, &self.monsters, frame);
+        self.add(&mut entities, &self.move_throttles, frame);
+        self.add(&mut entities, &self.obstacles, frame);
+        self.add(&mut entities, &self.player_stats, frame);
+        self.add(&mut entities, &self.players, frame);
+        self.add(&mut entities, &self.target_memories, frame);
+        self.add(&mut entities, &self.transforms, frame);
+        self.add(&mut entities, &self.weapons, frame);
+
+        if entities.is_empty() {
+            warn!("Desync frame {frame} is no longer in the snapshot history");
+        }
+
+        DesyncReport {
+            entities: entities
+                .into_iter()
+                .map(|(order, components)| EntitySnapshot { components, order })
+                .collect(),
+            frame,
+            local_checksum,
+            player_id,
+            random_counter: self.rng.peek(frame).cloned().flatten().map(|r| r.counter),
+            remote_checksum,
+        }
+    }
+
+    fn add<C: Debug>(
+        &self,
+        entities: &mut EntityComponents,
+        snapshots: &GgrsComponentSnapshots<C>,
+        frame: i32,
+    ) {
+        let Some(snapshot) = snapshots.peek(frame) else {
+            return;
+        };
+
+        let name = std::any::type_name::<C>()
+            .rsplit("::")
+            .next()
+            .unwrap_or("(Unknown)");
+
+        for (&rollback, component) in snapshot.iter() {
+            entities
+                .entry(self.ordered.order(rollback))
+                .or_default()
+                .insert(name.to_string(), format!("{component:?}"));
+        }
+    }
+}
@@ -1,28 +1,38 @@
-use crate::{common::DesyncEvent, monsters::Monster, player::MoveThrottle, prelude::*};
-use bevy_ggrs::{
-    ggrs::GgrsEvent, GgrsComponentSnapshots, GgrsResourceSnapshots, GgrsSnapshots, LocalPlayers,
-    Session,
-};
-use std::fmt::Debug;
-use std::{fs::OpenOptions, io::Write};
+use super::{DesyncReport, DesyncSnapshots, PeerConnections};
+use crate::{common::DesyncEvent, prelude::*};
+use bevy_ggrs::{ggrs::GgrsEvent, LocalPlayers, Session};
+use std::path::PathBuf;
 
 pub fn handle_ggrs_events(
     mut event_writer: EventWriter<DesyncEvent>,
     mut next_state: ResMut<NextState<GameState>>,
     mut session: ResMut<Session<config::GgrsSessionConfig>>,
     local_players: Res<LocalPlayers>,
-    monster_snapshots: Res<GgrsComponentSnapshots<Monster>>,
-    player_movement_snapshots: Res<GgrsComponentSnapshots<MoveThrottle>>,
-    player_snapshots: Res<GgrsComponentSnapshots<Player>>,
-    rng_snapshots: Res<GgrsResourceSnapshots<RandomGenerator>>,
-    transform_snapshots: Res<GgrsComponentSnapshots<Transform>>,
+    mut peer_connections: ResMut<PeerConnections>,
+    snapshots: DesyncSnapshots,
 ) {
     match session.as_mut() {
         Session::P2P(s) => {
-            for event in s.events() {
+            let events = s.events().collect::<Vec<_>>();
+            for event in events {
                 match event {
-                    GgrsEvent::Disconnected { .. } | GgrsEvent::NetworkInterrupted { .. } => {
-                        warn!("GGRS event: {event:?}")
+                    GgrsEvent::Disconnected { addr } => {
+                        warn!("GGRS event: {event:?}");
+                        s.handles_by_address(addr)
+                            .into_iter()
+                            .for_each(|player_id| peer_connections.disconnect(player_id));
+                    }
+                    GgrsEvent::NetworkInterrupted { addr, .. } => {
+                        warn!("GGRS event: {event:?}");
+                        s.handles_by_address(addr)
+                            .into_iter()
+                            .for_each(|player_id| peer_connections.interrupt(player_id));
+                    }
+                    GgrsEvent::NetworkResumed { addr } => {
+                        info!("GGRS event: {event:?}");
+                        s.handles_by_address(addr)
+                            .into_iter()
+                            .for_each(|player_id| peer_connections.resume(player_id));
                     }
                     GgrsEvent::DesyncDetected {
                         frame,
@@ -35,17 +45,10 @@ pub fn handle_ggrs_events(
                             "GGRS event: Desync on frame {frame} player {player_id}. \
                          Local checksum: {local_checksum:X}, remote checksum: {remote_checksum:X}"
                         );
-                        // Note the below is not useful unless bevy_ggrs keeps enough snapshots around
-                        // See [issue](https://github.com/gschup/bevy_ggrs/issues/117)
-                        log_component_snapshot(&monster_snapshots, frame);
-                        log_component_snapshot(&player_movement_snapshots, frame);
-                        let player_entity_info = log_component_snapshot(&player_snapshots, frame);
-                        log_res_snapshot(&rng_snapshots, frame);
-                        log_component_snapshot(&transform_snapshots, frame);
-
-                        log_to_file(&transform_snapshots, frame, player_id, &player_entity_info);
+                        let report =
+                            snapshots.report(frame, local_checksum, player_id, remote_checksum);
+                        save_report(&report);
 
-                        info!("Pausing game. Press P to take a snapshot of monster moves.");
                         event_writer.send(DesyncEvent { frame });
                         next_state.set(GameState::Paused);
                     }
@@ -69,11 +72,7 @@ pub fn handle_ggrs_events(
         //                     "GGRSEvent::MismatchedChecksum: Detected checksum mismatch during rollback \
         //                      on frame {current_frame}, oldest mismatched frame: {frame}. Player={player_id}"
         //                 );
-        //                 log_component_snapshot(&monster_snapshots, frame);
-        //                 log_component_snapshot(&player_movement_snapshots, frame);
-        //                 log_component_snapshot(&player_snapshots, frame);
-        //                 log_res_snapshot(&rng_snapshots, frame);
-        //                 log_component_snapshot(&transform_snapshots, frame);
+        //                 save_report(&snapshots.report(frame, 0, player_id, 0));
 
         //                 assert_eq!(player_id, 0);
         //             }
@@ -85,99 +84,18 @@ pub fn handle_ggrs_events(
     }
 }
 
-fn log_to_file(
-    container: &GgrsComponentSnapshots<Transform>,
-    frame: i32,
-    player_id: usize,
-    player_entity_info: &str,
-) {
-    let Some(snapshots) = container.peek(frame) else {
-        return;
-    };
-
-    let mut rows = snapshots
-        .iter()
-        .map(|(r, t)| format!("{r:?},{},{}", t.translation.x, t.translation.y))
-        .collect::<Vec<_>>();
-    rows.sort();
-
-    let mut file = OpenOptions::new()
-        .create(true)
-        .append(true)
-        .open(format!("{player_id}_transforms.csv"))
-        .expect("Unable to create file");
-
-    writeln!(
-        file,
-        "Frame,{frame},player,{player_id},player_entity,{player_entity_info}"
-    )
-    .expect("error writing row");
-
-    rows.iter().for_each(|r| {
-        writeln!(file, "{r}").expect("error writing row");
-    });
-}
-
-fn log_component_snapshot<T: Debug>(container: &GgrsComponentSnapshots<T>, frame: i32) -> String {
-    let name = get_name::<T>();
-    let Some(snapshots) = container.peek(frame) else {
-        handle_unavailable_snapshot(&name, container, frame, |s| s.iter().count());
-        return String::new();
-    };
-
-    info!(
-        "Frame {frame} {name} snapshots {}",
-        snapshots.iter().count()
-    );
-    snapshots
-        .iter()
-        .fold(String::new(), |acc, (rollback, component)| {
-            info!("{name} snapshot: {component:?} [{rollback:?}]");
-            match acc {
-                acc if !acc.is_empty() => acc,
-                // return the entity id within the string of format "Rollback(5238v1#4294972534)"
-                _ => format!("{rollback:?}")[9..26].to_string(),
+/// Save the report to the working directory, or log it where there is no file
+/// system (i.e. WASM) so it can be copied from the console.
+fn save_report(report: &DesyncReport) {
+    let path = PathBuf::from(report.file_name());
+    match report.save(&path) {
+        Ok(()) => info!("Saved desync report to {}", path.display()),
+        Err(e) => {
+            warn!("{e}");
+            match report.to_ron() {
+                Ok(ron) => info!("Desync report:\n{ron}"),
+                Err(e) => error!("{e}"),
             }
-        })
-}
-
-fn log_res_snapshot<T: std::fmt::Debug>(container: &GgrsResourceSnapshots<T>, frame: i32) {
-    let name = get_name::<T>();
-    let Some(Some(snapshot)) = container.peek(frame) else {
-        return handle_unavailable_snapshot(&name, container, frame, |_| 1);
-    };
-
-    info!("Frame {frame} {name} resource {:?}", snapshot);
-}
-
-fn get_name<T>() -> String {
-    std::any::type_name::<T>()
-        .split("::")
-        .last()
-        .unwrap_or("(Unknown)")
-        .to_string()
-}
-
-fn handle_unavailable_snapshot<T, S, U>(
-    name: &str,
-    _container: &GgrsSnapshots<T, S>,
-    frame: i32,
-    _get_count: U,
-) where
-    T: Debug,
-    U: Fn(&S) -> usize,
-{
-    warn!("Desync frame {frame} unavailable in {name} snapshot history");
-    // TODO below require bevy_ggrs P2PSession::frames to be public
-    // info!(
-    //     "{name} history contains only {} frames as follows:",
-    //     container.frames.len()
-    // );
-    // container.frames.iter().for_each(|s_frame| {
-    //     let snapshot_count = container
-    //         .peek(*s_frame)
-    //         .map(|snapshot| get_count(snapshot))
-    //         .unwrap_or_default();
-    //     info!("\tFrame {s_frame} with {snapshot_count} snapshots");
-    // });
+        }
+    }
 }
@@ -0,0 +1,96 @@
+use crate::{
+    game_states::{tear_down_game, GameEntityQuery},
+    player::PlayerId,
+    prelude::*,
+    startup::{MatchboxRoom, RoomCodeInput},
+};
+use bevy_ggrs::{GgrsTime, Session};
+use bevy_matchbox::MatchboxSocket;
+
+/// Connection state of the remote players, updated from GGRS events so the HUD
+/// can report it. GGRS itself decides when an interrupted player is disconnected
+/// (see [`config::GGRS_DISCONNECT_TIMEOUT_SECONDS`]).
+#[derive(Debug, Default, Resource)]
+pub struct PeerConnections {
+    /// Remote players that timed out and were dropped from the game
+    pub disconnected: Vec<PlayerId>,
+    /// Remote players GGRS has not heard from lately
+    pub interrupted: Vec<PlayerId>,
+    /// A disconnect has not yet been acknowledged by the local player
+    pub notice_pending: bool,
+}
+
+impl PeerConnections {
+    pub fn disconnect(&mut self, player_id: PlayerId) {
+        self.interrupted.retain(|id| *id != player_id);
+        if !self.disconnected.contains(&player_id) {
+            self.disconnected.push(player_id);
+            self.notice_pending = true;
+        }
+    }
+
+    pub fn interrupt(&mut self, player_id: PlayerId) {
+        if !self.interrupted.contains(&player_id) {
+            self.interrupted.push(player_id);
+        }
+    }
+
+    pub fn resume(&mut self, player_id: PlayerId) {
+        self.interrupted.retain(|id| *id != player_id);
+    }
+}
+
+/// Once a player has been dropped, the remaining player presses C to carry on
+/// or L to leave the game (torn down like for a restart) and return to the lobby.
+pub fn respond_to_disconnect(
+    mut commands: Commands,
+    entities: GameEntityQuery,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut peer_connections: ResMut<PeerConnections>,
+) {
+    if keys.just_pressed(KeyCode::KeyC) {
+        info!("Continuing without disconnected players");
+        peer_connections.notice_pending = false;
+    } else if keys.just_pressed(KeyCode::KeyL) {
+        info!("Leaving game to return to the lobby");
+        tear_down_game(&mut commands, &entities);
+        commands.remove_resource::<MatchboxRoom>();
+        commands.remove_resource::<MatchboxSocket>();
+        commands.remove_resource::<Session<config::GgrsSessionConfig>>();
+        // GGRS restarts its frame count for the next session, but not its clock
+        commands.insert_resource(Time::<GgrsTime>::default());
+        commands.insert_resource(PeerConnections::default());
+        commands.insert_resource(RoomCodeInput::default());
+        next_state.set(GameState::Lobby);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interrupted_then_resumed() {
+        let mut connections = PeerConnections::default();
+
+        connections.interrupt(1);
+        connections.resume(1);
+
+        assert!(connections.interrupted.is_empty());
+        assert!(!connections.notice_pending);
+    }
+
+    #[test]
+    fn interrupted_then_disconnected() {
+        let mut connections = PeerConnections::default();
+
+        connections.interrupt(1);
+        connections.interrupt(2);
+        connections.disconnect(1);
+
+        assert_eq!(connections.interrupted, vec![2]);
+        assert_eq!(connections.disconnected, vec![1]);
+        assert!(connections.notice_pending);
+    }
+}
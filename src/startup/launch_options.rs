@@ -0,0 +1,293 @@
+use super::MatchboxRoom;
+use crate::prelude::*;
+use std::path::PathBuf;
+
+/// Options given at launch, either as command line arguments (e.g.
+/// `--mode single`) natively or as URL query parameters (e.g. `?mode=multi`)
+/// in the browser. Anything not given is chosen in the main menu or lobby.
+#[derive(Debug, Default, Resource)]
+pub struct LaunchOptions {
+    pub game_mode: Option<GameMode>,
+    /// Number of players in multiplayer and sync test sessions
+    pub num_players: Option<usize>,
+    /// File to record a multiplayer or sync test game to (native only)
+    pub record: Option<PathBuf>,
+    /// Recorded game to play back, which sets the game mode to replay
+    pub replay: Option<PathBuf>,
+    /// Multiplayer room code to join, skipping the lobby
+    pub room: Option<String>,
+    /// Random seed for single player and sync test games, to replay a dungeon.
+    /// P2P games derive theirs from the peer ids.
+    pub seed: Option<u64>,
+    /// Matchbox signaling server base URL, e.g. `ws://127.0.0.1:3536`
+    pub server: Option<String>,
+    /// Check each replayed frame against the recorded checksum
+    pub verify_replay: bool,
+}
+
+impl LaunchOptions {
+    /// Natively, the `MATCHBOX_SERVER` env var is used if `--server` is not given.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn parse() -> Self {
+        let mut options = Self::from_pairs(Self::parse_args(std::env::args().skip(1)));
+        options.server = options
+            .server
+            .or_else(|| std::env::var("MATCHBOX_SERVER").ok());
+
+        options
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn parse() -> Self {
+        let query = web_sys::window()
+            .and_then(|window| window.location().search().ok())
+            .unwrap_or_default();
+
+        Self::from_pairs(Self::parse_query(&query))
+    }
+
+    /// Panics on invalid values so a typo is not silently ignored. Unknown
+    /// options are ignored since the page URL may carry unrelated parameters.
+    fn from_pairs(pairs: Vec<(String, String)>) -> Self {
+        let mut options = Self::default();
+
+        for (key, value) in pairs {
+            match key.as_str() {
+                "mode" => {
+                    options.game_mode = Some(
+                        value
+                            .parse()
+                            .unwrap_or_else(|e| panic!("Invalid launch option: {e}")),
+                    )
+                }
+                "players" => {
+                    options.num_players = Some(
+                        NumPlayers::parse(&value)
+                            .unwrap_or_else(|e| panic!("Invalid launch option: {e}")),
+                    )
+                }
+                "record" => options.record = Some(PathBuf::from(value)),
+                "replay" => {
+                    options.game_mode = Some(GameMode::Replay);
+                    options.replay = Some(PathBuf::from(value));
+                }
+                "room" => {
+                    options.room = Some(
+                        MatchboxRoom::parse_code(&value)
+                            .unwrap_or_else(|e| panic!("Invalid launch option: {e}")),
+                    )
+                }
+                "seed" => {
+                    options.seed = Some(
+                        value
+                            .parse()
+                            .unwrap_or_else(|_| panic!("Invalid launch option: seed '{value}'")),
+                    )
+                }
+                "server" => options.server = Some(value),
+                "verify" => options.verify_replay = true,
+                _ => {}
+            }
+        }
+
+        options
+    }
+
+    /// The signaling server given at launch or the default one.
+    pub fn server_url(&self) -> &str {
+        self.server
+            .as_deref()
+            .unwrap_or(config::MATCHBOX_SERVER_URL)
+    }
+
+    /// Accepts both `--key value` and `--key=value`.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    fn parse_args(args: impl Iterator<Item = String>) -> Vec<(String, String)> {
+        let mut args = args.peekable();
+        let mut pairs = vec![];
+
+        while let Some(arg) = args.next() {
+            let Some(option) = arg.strip_prefix("--") else {
+                continue;
+            };
+
+            let pair = match option.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => {
+                    let value = args.next_if(|next| !next.starts_with("--"));
+                    (option.to_string(), value.unwrap_or_default())
+                }
+            };
+            pairs.push(pair);
+        }
+
+        pairs
+    }
+
+    /// Keys and values are percent-decoded (e.g. `server=ws%3A%2F%2Fhost`).
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+    fn parse_query(query: &str) -> Vec<(String, String)> {
+        query
+            .trim_start_matches('?')
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (percent_decode(key), percent_decode(value)))
+            .collect()
+    }
+}
+
+/// Decodes `%XX` escapes and `+` (a space in query strings). Invalid escapes
+/// are kept as is.
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+fn percent_decode(encoded: &str) -> String {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (None, b'+') => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (None, byte) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::separate_value(&["--mode", "single"], Some(GameMode::SinglePlayer))]
+    #[case::inline_value(&["--mode=multi"], Some(GameMode::MultiPlayer))]
+    #[case::unknown_option(&["--foo", "bar", "--mode", "synctest"], Some(GameMode::GgrsSyncTest))]
+    #[case::no_options(&[], None)]
+    fn parse_args(#[case] args: &[&str], #[case] expected: Option<GameMode>) {
+        let args = args.iter().map(|arg| arg.to_string());
+
+        let options = LaunchOptions::from_pairs(LaunchOptions::parse_args(args));
+
+        assert_eq!(options.game_mode, expected);
+    }
+
+    #[test]
+    fn parse_query() {
+        let pairs = LaunchOptions::parse_query("?ref=abc&room=wxyz&mode=multi&seed=7");
+
+        let options = LaunchOptions::from_pairs(pairs);
+
+        assert_eq!(options.game_mode, Some(GameMode::MultiPlayer));
+        assert_eq!(options.room.as_deref(), Some("WXYZ"));
+        assert_eq!(options.seed, Some(7));
+    }
+
+    #[test]
+    fn parse_encoded_query() {
+        let pairs = LaunchOptions::parse_query("?server=ws%3A%2F%2Fhost%3A3536&mo%64e=single");
+
+        let options = LaunchOptions::from_pairs(pairs);
+
+        assert_eq!(options.server_url(), "ws://host:3536");
+        assert_eq!(options.game_mode, Some(GameMode::SinglePlayer));
+    }
+
+    #[rstest]
+    #[case::escapes("a%2Fb%20c", "a/b c")]
+    #[case::plus("a+b", "a b")]
+    #[case::invalid_escape("100%", "100%")]
+    #[case::not_hex("%zz", "%zz")]
+    fn percent_decode(#[case] encoded: &str, #[case] expected: &str) {
+        assert_eq!(super::percent_decode(encoded), expected);
+    }
+
+    #[rstest]
+    #[case::given(&["--server=ws://127.0.0.1:3536"], "ws://127.0.0.1:3536")]
+    #[case::default(&[], config::MATCHBOX_SERVER_URL)]
+    fn server_url(#[case] args: &[&str], #[case] expected: &str) {
+        let args = args.iter().map(|arg| arg.to_string());
+
+        let options = LaunchOptions::from_pairs(LaunchOptions::parse_args(args));
+
+        assert_eq!(options.server_url(), expected);
+    }
+
+    #[rstest]
+    #[case::given(&["--players", "4"], Some(4))]
+    #[case::not_given(&["--mode", "multi"], None)]
+    fn num_players(#[case] args: &[&str], #[case] expected: Option<usize>) {
+        let args = args.iter().map(|arg| arg.to_string());
+
+        let options = LaunchOptions::from_pairs(LaunchOptions::parse_args(args));
+
+        assert_eq!(options.num_players, expected);
+    }
+
+    #[rstest]
+    #[should_panic(expected = "Invalid player count")]
+    #[case::too_few("1")]
+    #[should_panic(expected = "Invalid player count")]
+    #[case::too_many("5")]
+    #[should_panic(expected = "Invalid player count")]
+    #[case::not_a_number("two")]
+    fn invalid_num_players(#[case] value: &str) {
+        LaunchOptions::from_pairs(vec![("players".to_string(), value.to_string())]);
+    }
+
+    #[test]
+    fn replay() {
+        let args = ["--replay", "game.replay", "--verify"].map(String::from);
+
+        let options = LaunchOptions::from_pairs(LaunchOptions::parse_args(args.into_iter()));
+
+        assert_eq!(options.game_mode, Some(GameMode::Replay));
+        assert_eq!(options.replay, Some(PathBuf::from("game.replay")));
+        assert!(options.verify_replay);
+    }
+
+    #[rstest]
+    #[case::cli(&["--seed", "12345"], Some(12345))]
+    #[case::not_given(&["--mode", "single"], None)]
+    fn seed(#[case] args: &[&str], #[case] expected: Option<u64>) {
+        let args = args.iter().map(|arg| arg.to_string());
+
+        let options = LaunchOptions::from_pairs(LaunchOptions::parse_args(args));
+
+        assert_eq!(options.seed, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid launch option: seed 'abc'")]
+    fn invalid_seed() {
+        LaunchOptions::from_pairs(vec![("seed".to_string(), "abc".to_string())]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid room code 'AB'")]
+    fn invalid_room() {
+        LaunchOptions::from_pairs(vec![("room".to_string(), "ab".to_string())]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown game mode 'solo'")]
+    fn invalid_mode() {
+        LaunchOptions::from_pairs(vec![("mode".to_string(), "solo".to_string())]);
+    }
+}
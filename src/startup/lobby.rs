@@ -0,0 +1,64 @@
+use super::{LaunchOptions, MatchboxRoom};
+use crate::prelude::*;
+use bevy::input::keyboard::{Key, KeyboardInput};
+
+/// The room code typed so far in the lobby.
+#[derive(Debug, Default, Resource)]
+pub struct RoomCodeInput(pub String);
+
+/// Type a room code and press Enter to join that room, or press Enter without
+/// a code to host a new room. Up and down arrows change the number of players,
+/// which must match that of the room being joined.
+pub fn type_room_code(
+    mut commands: Commands,
+    mut input: ResMut<RoomCodeInput>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    launch_options: Res<LaunchOptions>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut num_players: ResMut<NumPlayers>,
+) {
+    for event in keyboard_events.read().filter(|e| e.state.is_pressed()) {
+        match &event.logical_key {
+            Key::ArrowDown => num_players.add(-1),
+            Key::ArrowUp => num_players.add(1),
+            Key::Backspace => {
+                input.0.pop();
+            }
+            Key::Character(c) => {
+                let mut chars = c.chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    if MatchboxRoom::is_code_char(c) && input.0.len() < config::ROOM_CODE_LENGTH {
+                        input.0.push(c.to_ascii_uppercase());
+                    }
+                }
+            }
+            Key::Enter => {
+                let server_url = launch_options.server_url();
+                let room = if input.0.is_empty() {
+                    MatchboxRoom::host(num_players.0, server_url)
+                } else if let Ok(code) = MatchboxRoom::parse_code(&input.0) {
+                    MatchboxRoom::new(code, num_players.0, server_url)
+                } else {
+                    continue; // incomplete code
+                };
+
+                join_room(room, &mut commands, &mut next_state);
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(super) fn join_room(
+    room: MatchboxRoom,
+    commands: &mut Commands,
+    next_state: &mut NextState<GameState>,
+) {
+    info!(
+        "Joining room {} for {} players on {}",
+        room.code, room.num_players, room.server_url
+    );
+    commands.insert_resource(room);
+    next_state.set(GameState::Startup);
+}
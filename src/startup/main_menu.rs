@@ -0,0 +1,66 @@
+use super::{lobby::join_room, LaunchOptions, MatchboxRoom};
+use crate::{
+    prelude::*,
+    replay::{Replay, ReplayPlayer},
+};
+
+/// Skip the main menu when the game mode was given as a launch option.
+pub fn enter_main_menu(
+    launch_options: Res<LaunchOptions>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if let Some(game_mode) = launch_options.game_mode {
+        info!("Game mode {game_mode:?} given at launch");
+        start(game_mode, &launch_options, &mut commands, &mut next_state);
+    }
+}
+
+/// Choose the game mode via the number keys listed on the main menu.
+pub fn choose_game_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    launch_options: Res<LaunchOptions>,
+    mut commands: Commands,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let game_mode = if keys.just_pressed(KeyCode::Digit1) {
+        GameMode::SinglePlayer
+    } else if keys.just_pressed(KeyCode::Digit2) {
+        GameMode::MultiPlayer
+    } else if keys.just_pressed(KeyCode::Digit3) {
+        GameMode::GgrsSyncTest
+    } else {
+        return;
+    };
+
+    info!("Game mode {game_mode:?} chosen in main menu");
+    start(game_mode, &launch_options, &mut commands, &mut next_state);
+}
+
+/// Multiplayer games go through the lobby to pick a room unless one was given
+/// at launch.
+fn start(
+    game_mode: GameMode,
+    launch_options: &LaunchOptions,
+    commands: &mut Commands,
+    next_state: &mut NextState<GameState>,
+) {
+    let mut num_players = NumPlayers::new(game_mode, launch_options.num_players);
+    if let Some(path) = launch_options.replay.as_ref() {
+        let replay = Replay::load(path).unwrap_or_else(|e| panic!("Invalid launch option: {e}"));
+        num_players = NumPlayers(replay.num_players);
+        commands.insert_resource(ReplayPlayer::new(replay, launch_options.verify_replay));
+    }
+    commands.insert_resource(game_mode);
+    commands.insert_resource(num_players);
+
+    match (game_mode, &launch_options.room) {
+        (GameMode::MultiPlayer, Some(code)) => join_room(
+            MatchboxRoom::new(code.clone(), num_players.0, launch_options.server_url()),
+            commands,
+            next_state,
+        ),
+        (GameMode::MultiPlayer, None) => next_state.set(GameState::Lobby),
+        _ => next_state.set(GameState::Startup),
+    }
+}
@@ -0,0 +1,101 @@
+use crate::prelude::*;
+use rand::{seq::IteratorRandom, thread_rng};
+
+/// The signaling server and room a multiplayer game connects to. Players using
+/// the same room code and player count on the same server are matched together.
+#[derive(Clone, Debug, Eq, PartialEq, Resource)]
+pub struct MatchboxRoom {
+    pub code: String,
+    pub num_players: usize,
+    pub server_url: String,
+}
+
+impl MatchboxRoom {
+    pub fn new(code: String, num_players: usize, server_url: &str) -> Self {
+        Self {
+            code,
+            num_players,
+            server_url: server_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Host a new game in a room with a randomly generated code to share
+    /// with the other players.
+    pub fn host(num_players: usize, server_url: &str) -> Self {
+        let mut rng = thread_rng();
+        let code = (0..config::ROOM_CODE_LENGTH)
+            .map(|_| {
+                config::ROOM_CODE_ALPHABET
+                    .chars()
+                    .choose(&mut rng)
+                    .expect("Inconceivable!")
+            })
+            .collect();
+
+        Self::new(code, num_players, server_url)
+    }
+
+    /// Returns true if the character may appear in a room code (ignoring case).
+    pub fn is_code_char(c: char) -> bool {
+        config::ROOM_CODE_ALPHABET.contains(c.to_ascii_uppercase())
+    }
+
+    /// Normalize a room code typed by a player to upper case, failing if it
+    /// has the wrong length or characters.
+    pub fn parse_code(code: &str) -> Result<String, String> {
+        let code = code.to_ascii_uppercase();
+        if code.len() == config::ROOM_CODE_LENGTH && code.chars().all(Self::is_code_char) {
+            Ok(code)
+        } else {
+            Err(format!(
+                "Invalid room code '{code}'. Use {} letters",
+                config::ROOM_CODE_LENGTH
+            ))
+        }
+    }
+
+    /// e.g. `ws://127.0.0.1:3536/dungeon_crawl_ABCD?next=2` where matchbox
+    /// groups the next 2 peers to join the room.
+    pub fn url(&self) -> String {
+        format!(
+            "{}/{}_{}?next={}",
+            self.server_url,
+            config::MATCHBOX_ROOM_PREFIX,
+            self.code,
+            self.num_players
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[test]
+    fn url() {
+        let room = MatchboxRoom::new("ABCD".to_string(), 3, "ws://127.0.0.1:3536/");
+
+        assert_eq!(room.url(), "ws://127.0.0.1:3536/dungeon_crawl_ABCD?next=3");
+    }
+
+    #[test]
+    fn host() {
+        let room = MatchboxRoom::host(config::NUM_PLAYERS, config::MATCHBOX_SERVER_URL);
+
+        assert_eq!(MatchboxRoom::parse_code(&room.code), Ok(room.code));
+    }
+
+    #[rstest]
+    #[case::upper_case("WXYZ", Ok("WXYZ"))]
+    #[case::lower_case("abcd", Ok("ABCD"))]
+    #[case::too_short("ABC", Err(()))]
+    #[case::too_long("ABCDE", Err(()))]
+    #[case::ambiguous_letter("ABIO", Err(()))]
+    #[case::digit("AB12", Err(()))]
+    fn parse_code(#[case] code: &str, #[case] expected: Result<&str, ()>) {
+        let parsed = MatchboxRoom::parse_code(code);
+
+        assert_eq!(parsed.map_err(|_| ()), expected.map(str::to_string));
+    }
+}
@@ -1,9 +1,15 @@
 use super::{
-    ggrs::{checksum_transform, create_p2p_session, handle_ggrs_events},
+    ggrs::{
+        checksum_transform, create_p2p_session, handle_ggrs_events, respond_to_disconnect,
+        PeerConnections,
+    },
+    lobby::{type_room_code, RoomCodeInput},
+    main_menu::{choose_game_mode, enter_main_menu},
     startup::startup,
 };
 use crate::{
-    common::{DungeonAssets, DungeonData},
+    common::{DungeonAssets, DungeonData, SpriteAssets, ThemeData},
+    dungeon::{BuilderData, PrefabData},
     hud,
     prelude::*,
 };
@@ -11,36 +17,66 @@ use bevy_asset_loader::prelude::*;
 use bevy_common_assets::ron::RonAssetPlugin;
 use bevy_ggrs::GgrsApp;
 
-pub struct StartupPlugin;
+/// Loads the fonts, sprites and dungeon data before showing the main menu.
+/// Kept apart from [`StartupPlugin`] so the headless app can skip it.
+pub struct AssetLoadingPlugin;
 
-impl Plugin for StartupPlugin {
+impl Plugin for AssetLoadingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(RonAssetPlugin::<DungeonData>::new(&["ron"]))
-            .add_loading_state(
-                LoadingState::new(GameState::Loading)
-                    .continue_to_state(GameState::Startup)
-                    .load_collection::<hud::FontAssets>()
-                    .load_collection::<DungeonAssets>(),
-            );
-
-        app.add_systems(OnEnter(GameState::Startup), startup);
+        app.add_plugins((
+            RonAssetPlugin::<BuilderData>::new(&["builders.ron"]),
+            RonAssetPlugin::<DungeonData>::new(&["ron"]),
+            RonAssetPlugin::<PrefabData>::new(&["prefabs.ron"]),
+            RonAssetPlugin::<ThemeData>::new(&["themes.ron"]),
+        ))
+        .add_loading_state(
+            LoadingState::new(GameState::Loading)
+                .continue_to_state(GameState::MainMenu)
+                .load_collection::<hud::FontAssets>()
+                .load_collection::<DungeonAssets>()
+                .load_collection::<SpriteAssets>(),
+        );
+    }
+}
 
-        if !game_mode(GameMode::SinglePlayer) {
-            app.rollback_resource_with_clone::<RandomGenerator>()
-                .checksum_resource_with_hash::<RandomGenerator>()
-                .rollback_component_with_clone::<Transform>()
-                .checksum_component::<Transform>(checksum_transform)
-                .rollback_component_with_copy::<Visibility>();
+pub struct StartupPlugin;
 
-            app.add_systems(
+impl Plugin for StartupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PeerConnections>()
+            .init_resource::<RoomCodeInput>()
+            .add_systems(OnEnter(GameState::MainMenu), enter_main_menu)
+            .add_systems(
                 Update,
                 (
-                    create_p2p_session.run_if(
-                        in_state(GameState::Startup).and(|| game_mode(GameMode::MultiPlayer)),
-                    ),
-                    handle_ggrs_events.run_if(in_state(GameState::InGame)),
+                    choose_game_mode.run_if(in_state(GameState::MainMenu)),
+                    type_room_code.run_if(in_state(GameState::Lobby)),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Startup), startup);
+
+        // Rollback is only active once a GGRS session starts, so registering
+        // is harmless in single player mode.
+        app.rollback_resource_with_clone::<RandomGenerator>()
+            .checksum_resource_with_hash::<RandomGenerator>()
+            .rollback_component_with_clone::<Transform>()
+            .checksum_component::<Transform>(checksum_transform)
+            .rollback_component_with_copy::<Visibility>();
+
+        app.add_systems(
+            Update,
+            (
+                create_p2p_session
+                    .run_if(in_state(GameState::Startup).and(game_mode(GameMode::MultiPlayer))),
+                handle_ggrs_events.run_if(
+                    in_state(GameState::InGame)
+                        .or(in_state(GameState::GameOver))
+                        .or(in_state(GameState::GameWon))
+                        .and(not(game_mode(GameMode::SinglePlayer))),
                 ),
-            );
-        }
+                respond_to_disconnect
+                    .run_if(|connections: Res<PeerConnections>| connections.notice_pending),
+            ),
+        );
     }
 }
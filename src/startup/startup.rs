@@ -1,6 +1,8 @@
+use super::{LaunchOptions, MatchboxRoom};
 use crate::{
     common::RandomGenerator,
-    config::{self, GameMode},
+    config::{self, GameMode, NumPlayers},
+    replay::ReplayPlayer,
     GameState,
 };
 use bevy::prelude::*;
@@ -10,16 +12,39 @@ use bevy_matchbox::{
     MatchboxSocket,
 };
 
-pub fn startup(mut commands: Commands, mut next_state: ResMut<NextState<GameState>>) {
-    match config::GAME_MODE {
-        GameMode::GgrsSyncTest => start_sync_test_session(&mut commands, &mut next_state),
-        GameMode::MultiPlayer => connect_to_matchbox(&mut commands),
-        GameMode::SinglePlayer => start_single_player_mode(&mut commands, &mut next_state),
+pub fn startup(
+    game_mode: Res<GameMode>,
+    mut commands: Commands,
+    launch_options: Res<LaunchOptions>,
+    mut next_state: ResMut<NextState<GameState>>,
+    num_players: Res<NumPlayers>,
+    replay_player: Option<Res<ReplayPlayer>>,
+    room: Option<Res<MatchboxRoom>>,
+) {
+    match *game_mode {
+        GameMode::GgrsSyncTest => start_sync_test_session(
+            num_players.0,
+            launch_options.seed,
+            &mut commands,
+            &mut next_state,
+        ),
+        GameMode::MultiPlayer => connect_to_matchbox(
+            &mut commands,
+            &room.expect("Room is chosen before starting a multiplayer game"),
+        ),
+        GameMode::Replay => start_replay_session(
+            &replay_player.expect("Replay is loaded before starting a replay"),
+            &mut commands,
+            &mut next_state,
+        ),
+        GameMode::SinglePlayer => {
+            start_single_player_mode(launch_options.seed, &mut commands, &mut next_state)
+        }
     }
 }
 
-fn connect_to_matchbox(commands: &mut Commands) {
-    let room_url = config::MATCHBOX_ROOM_URL;
+fn connect_to_matchbox(commands: &mut Commands, room: &MatchboxRoom) {
+    let room_url = room.url();
     info!("Connecting to matchbox server {room_url}");
     let socket: MatchboxSocket = WebRtcSocketBuilder::new(room_url)
         .add_channel(ChannelConfig::unreliable())
@@ -27,19 +52,70 @@ fn connect_to_matchbox(commands: &mut Commands) {
     commands.insert_resource(socket);
 }
 
-fn start_single_player_mode(commands: &mut Commands, next_state: &mut NextState<GameState>) {
+/// Replay the recorded inputs through a sync test session without rollbacks
+/// (check distance 0), so the same GgrsSchedule systems run in fixed time steps.
+fn start_replay_session(
+    replay_player: &ReplayPlayer,
+    commands: &mut Commands,
+    next_state: &mut NextState<GameState>,
+) {
+    let replay = &replay_player.replay;
+    info!(
+        "Starting replay of {} frames for {} players",
+        replay.len(),
+        replay.num_players
+    );
+    let mut session_builder = ggrs::SessionBuilder::<config::GgrsSessionConfig>::new()
+        .with_num_players(replay.num_players)
+        .with_max_prediction_window(config::GGRS_MAX_PREDICTION)
+        .with_check_distance(0);
+
+    for i in 0..replay.num_players {
+        session_builder = session_builder
+            .add_player(ggrs::PlayerType::Local, i)
+            .expect("Failed to add player");
+    }
+
+    let ggrs_session = session_builder
+        .start_synctest_session()
+        .expect("Failed to start session");
+
+    commands.insert_resource(bevy_ggrs::Session::SyncTest(ggrs_session));
+    commands.insert_resource(RandomGenerator::from_seed(replay.seed));
+    next_state.set(GameState::InGame);
+}
+
+/// Seed from the launch options if given, so a dungeon can be played again.
+fn random_generator(seed: Option<u64>) -> RandomGenerator {
+    let rng = seed.map_or_else(RandomGenerator::new, RandomGenerator::from_seed);
+    info!("Random seed {}", rng.seed);
+
+    rng
+}
+
+fn start_single_player_mode(
+    seed: Option<u64>,
+    commands: &mut Commands,
+    next_state: &mut NextState<GameState>,
+) {
     info!("Starting single player game.");
-    commands.insert_resource(RandomGenerator::new());
+    commands.insert_resource(random_generator(seed));
     next_state.set(GameState::InGame);
 }
 
-fn start_sync_test_session(commands: &mut Commands, next_state: &mut NextState<GameState>) {
-    info!("Starting sync-test session");
+fn start_sync_test_session(
+    num_players: usize,
+    seed: Option<u64>,
+    commands: &mut Commands,
+    next_state: &mut NextState<GameState>,
+) {
+    info!("Starting sync-test session for {num_players} players");
     let mut session_builder = ggrs::SessionBuilder::<config::GgrsSessionConfig>::new()
-        .with_num_players(config::NUM_PLAYERS)
+        .with_num_players(num_players)
+        .with_max_prediction_window(config::GGRS_MAX_PREDICTION)
         .with_desync_detection_mode(DesyncDetection::On { interval: 1 });
 
-    for i in 0..config::NUM_PLAYERS {
+    for i in 0..num_players {
         session_builder = session_builder
             .add_player(ggrs::PlayerType::Local, i)
             .expect("Failed to add player");
@@ -50,6 +126,6 @@ fn start_sync_test_session(commands: &mut Commands, next_state: &mut NextState<G
         .expect("Failed to start session");
 
     commands.insert_resource(bevy_ggrs::Session::SyncTest(ggrs_session));
-    commands.insert_resource(RandomGenerator::new());
+    commands.insert_resource(random_generator(seed));
     next_state.set(GameState::InGame);
 }
@@ -0,0 +1,270 @@
+use bevy::input::{
+    keyboard::{Key, KeyboardInput},
+    ButtonState,
+};
+use dungeon_crawl_p2p::{
+    common::PendingChange,
+    dungeon::{DungeonPosition, WallTile},
+    fov::RecalculateFovEvent,
+    game_states::Pause,
+    headless::HeadlessApp,
+    items::{Grabbable, MagicItem},
+    monsters::Monster,
+    player::{Inventory, MoveDirection, Obstacle, PlayerAction},
+    prelude::*,
+    startup::PeerConnections,
+};
+
+const SEED: u64 = 42;
+
+#[test]
+fn player_attacks_monster() {
+    let mut app = HeadlessApp::new(1, SEED);
+    let player = app.player(0).expect("Player 0 not spawned");
+    let (direction, pos) = free_neighbor(&mut app, player);
+    let monster = place_monster(&mut app, pos);
+    let health = app.world().get::<Health>(monster).unwrap().current;
+
+    app.step(&[PlayerAction::Move(direction)]);
+
+    let revised_health = app.world().get::<Health>(monster).map_or(0, |h| h.current);
+    assert!(revised_health < health);
+}
+
+#[test]
+fn player_grabs_item() {
+    let mut app = HeadlessApp::new(1, SEED);
+    let player = app.player(0).expect("Player 0 not spawned");
+    let transform = *app.world().get::<Transform>(player).unwrap();
+    let item = app
+        .world_mut()
+        .spawn((MagicItem::HealingPotion(3), Grabbable, transform))
+        .id();
+
+    app.step(&[PlayerAction::GrabItem]);
+
+    let inventory = app.world().get::<Inventory>(player).unwrap();
+    assert!(matches!(
+        inventory.items.as_slice(),
+        [MagicItem::HealingPotion(3)]
+    ));
+    assert!(app.world().get_entity(item).is_err());
+}
+
+#[test]
+fn fov_follows_player() {
+    let mut app = HeadlessApp::new(2, SEED);
+    let player = app.player(1).expect("Player 1 not spawned");
+    let (direction, pos) = free_neighbor(&mut app, player);
+
+    app.step(&[PlayerAction::None, PlayerAction::Move(direction)]);
+    app.idle(1);
+
+    assert_eq!(position(&app, player), pos);
+    let fov = app.world().get::<FieldOfView>(player).unwrap();
+    assert!(fov.visible_tiles.contains_key(&ivec2(pos)));
+    assert!(fov
+        .visible_tiles
+        .keys()
+        .all(|tile| (*tile - ivec2(pos)).abs().max_element() <= fov.radius as i32));
+}
+
+#[test]
+fn only_players_see_walls() {
+    let mut app = HeadlessApp::new(1, SEED);
+    let player = app.player(0).expect("Player 0 not spawned");
+    let start = position(&app, player);
+    let (direction, _) = free_neighbor(&mut app, player);
+    app.step(&[PlayerAction::Move(direction)]);
+    let monster = place_monster(&mut app, start);
+    app.world_mut()
+        .send_event(RecalculateFovEvent::new(monster, ivec2(start)));
+    app.idle(1);
+
+    let walls: Vec<Entity> = app
+        .world_mut()
+        .query_filtered::<Entity, With<WallTile>>()
+        .iter(app.world())
+        .collect();
+    let sees_walls = |entity| {
+        let fov = app.world().get::<FieldOfView>(entity).unwrap();
+        fov.visible_tiles.values().any(|tile| walls.contains(tile))
+    };
+
+    let monster_fov = app.world().get::<FieldOfView>(monster).unwrap();
+    assert!(!monster_fov.visible_tiles.is_empty());
+    assert!(sees_walls(player));
+    assert!(!sees_walls(monster));
+}
+
+#[test]
+fn monster_attacks_adjacent_player() {
+    let mut app = HeadlessApp::new(1, SEED);
+    let player = app.player(0).expect("Player 0 not spawned");
+    let (_, pos) = free_neighbor(&mut app, player);
+    let monster = place_monster(&mut app, pos);
+    app.world_mut()
+        .send_event(RecalculateFovEvent::new(monster, ivec2(pos)));
+
+    let attacked = (0..600).any(|_| {
+        app.idle(1);
+        app.world()
+            .get::<Health>(player)
+            .is_none_or(|health| health.current < health.max)
+    });
+
+    assert!(attacked, "Monster never attacked the player");
+}
+
+#[test]
+fn pause_freezes_game() {
+    let mut app = HeadlessApp::new(1, SEED);
+    let player = app.player(0).expect("Player 0 not spawned");
+    let (direction, pos) = free_neighbor(&mut app, player);
+    let start = position(&app, player);
+
+    app.step(&[PlayerAction::Pause]);
+    for _ in 0..10 {
+        app.step(&[PlayerAction::Move(direction)]);
+    }
+
+    assert!(app.world().resource::<Pause>().is_paused());
+    assert_eq!(position(&app, player), start);
+
+    app.step(&[PlayerAction::Pause]);
+    app.step(&[PlayerAction::Move(direction)]);
+
+    assert!(!app.world().resource::<Pause>().is_paused());
+    assert_eq!(position(&app, player), pos);
+}
+
+#[test]
+fn restart_from_pause() {
+    let mut app = HeadlessApp::new(1, SEED);
+    let player = app.player(0).expect("Player 0 not spawned");
+
+    app.step(&[PlayerAction::Pause]);
+    app.step(&[PlayerAction::Restart]);
+    app.idle(config::GGRS_MAX_PREDICTION - 1);
+
+    assert!(app.world().get_entity(player).is_ok(), "Restarted early");
+
+    app.idle(1);
+
+    assert_eq!(app.state(), GameState::InGame);
+    assert!(!app.world().resource::<Pause>().is_paused());
+    assert_ne!(app.world().resource::<RandomGenerator>().seed, SEED);
+    assert!(app.world().get_entity(player).is_err());
+    let players = app.world_mut().query::<&Player>().iter(app.world()).count();
+    assert_eq!(players, 1);
+}
+
+#[test]
+fn leave_while_paused() {
+    let mut app = HeadlessApp::new(1, SEED);
+    let player = app.player(0).expect("Player 0 not spawned");
+    app.step(&[PlayerAction::Pause]);
+    app.step(&[PlayerAction::Restart]);
+    app.world_mut()
+        .resource_mut::<PeerConnections>()
+        .notice_pending = true;
+
+    press_key(&mut app, KeyCode::KeyL, Key::Character("l".into()));
+    app.update();
+    app.update();
+
+    assert_eq!(app.state(), GameState::Lobby);
+    assert!(app.world().get_resource::<DungeonMap>().is_none());
+
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Startup);
+    while app.state() != GameState::InGame {
+        app.update();
+    }
+
+    assert!(!app.world().resource::<Pause>().is_paused());
+    assert_eq!(app.world().resource::<PendingChange>().change(), None);
+    assert!(app.world().get_entity(player).is_err());
+    let players = app.world_mut().query::<&Player>().iter(app.world()).count();
+    assert_eq!(players, 1);
+}
+
+#[test]
+fn seed_regenerates_dungeon() {
+    let layout = |app: &HeadlessApp| {
+        let dungeon = app.world().resource::<DungeonMap>();
+        let walkable = dungeon
+            .tiles()
+            .map(|tile| tile.pos)
+            .filter(|pos| dungeon.is_walkable(pos))
+            .collect::<Vec<_>>();
+
+        (walkable, dungeon.player_starting_positions.clone())
+    };
+    let first = HeadlessApp::new(1, SEED);
+    let second = HeadlessApp::new(1, SEED);
+
+    assert_eq!(second.world().resource::<RandomGenerator>().seed, SEED);
+    assert_eq!(layout(&first), layout(&second));
+}
+
+/// A walkable position next to the entity that is not occupied by another
+/// player or monster, along with the direction to it.
+fn free_neighbor(app: &mut HeadlessApp, entity: Entity) -> (MoveDirection, DungeonPosition) {
+    let pos = position(app, entity);
+    let occupied: Vec<DungeonPosition> = app
+        .world_mut()
+        .query_filtered::<&Transform, With<Obstacle>>()
+        .iter(app.world())
+        .map(|t| DungeonPosition::from_vec3(t.translation))
+        .collect();
+    let map = app.world().resource::<DungeonMap>();
+
+    [
+        MoveDirection::Up,
+        MoveDirection::Down,
+        MoveDirection::Left,
+        MoveDirection::Right,
+    ]
+    .into_iter()
+    .map(|direction| {
+        let offset = direction.to_ivec2();
+        let target = DungeonPosition::new(pos.x + offset.x as isize, pos.y + offset.y as isize);
+        (direction, target)
+    })
+    .find(|(_, target)| map.is_walkable(target) && !occupied.contains(target))
+    .expect("Player is boxed in")
+}
+
+fn ivec2(pos: DungeonPosition) -> IVec2 {
+    pos.to_vec2().as_ivec2()
+}
+
+/// Move the first monster to the given position.
+fn place_monster(app: &mut HeadlessApp, pos: DungeonPosition) -> Entity {
+    let mut monsters = app
+        .world_mut()
+        .query_filtered::<(Entity, &mut Transform), With<Monster>>();
+    let (monster, mut transform) = monsters
+        .iter_mut(app.world_mut())
+        .next()
+        .expect("No monsters spawned");
+    transform.translation = pos.to_vec3(transform.translation.z);
+
+    monster
+}
+
+fn press_key(app: &mut HeadlessApp, key_code: KeyCode, logical_key: Key) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key,
+        repeat: false,
+        state: ButtonState::Pressed,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+fn position(app: &HeadlessApp, entity: Entity) -> DungeonPosition {
+    DungeonPosition::from_vec3(app.world().get::<Transform>(entity).unwrap().translation)
+}
@@ -0,0 +1,486 @@
+use bevy_ggrs::{AddRollbackCommandExtension, AdvanceWorld, AdvanceWorldSet, RollbackFrameCount};
+use dungeon_crawl_p2p::{
+    dungeon::DungeonPosition,
+    fov::RecalculateFovEvent,
+    game_states::{GameOverReport, Pause, Winner},
+    headless::{random_script, NetworkConditions, TwoPeerHarness},
+    items::{Grabbable, MagicItem},
+    monsters::Monster,
+    player::{MoveDirection, PlayerAction},
+    prelude::*,
+    replay::Replay,
+};
+use rstest::rstest;
+
+const FRAMES: i32 = 300;
+const SEED: u64 = 7;
+/// Frame on which player 0 is put next to the exit
+const STAIRS_FRAME: i32 = 20;
+/// Frame on which the Amulet of Yala appears under player 0
+const AMULET_FRAME: i32 = 27;
+/// Frame on which a monster is put next to player 0, who is left with 1 hp
+const KILL_FRAME: i32 = 20;
+/// Frame from which player 1 holds restart once the game is over
+const RESTART_GAME_OVER_FRAME: i32 = 200;
+/// Frame on which player 0 pauses the game
+const PAUSE_FRAME: i32 = 30;
+/// Frame on which player 1 presses restart in the pause menu
+const RESTART_FRAME: i32 = 50;
+
+#[rstest]
+#[case::perfect_network(0, 0.)]
+#[case::latency(3, 0.)]
+#[case::latency_and_packet_loss(2, 0.1)]
+fn peers_stay_in_sync(#[case] latency: usize, #[case] packet_loss: f64) {
+    let conditions = NetworkConditions {
+        latency,
+        packet_loss,
+    };
+    let mut harness = TwoPeerHarness::new(SEED, conditions, FRAMES as usize);
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+}
+
+#[rstest]
+#[case::latency(3, 0.)]
+#[case::latency_and_packet_loss(2, 0.1)]
+fn peers_descend_in_sync(#[case] latency: usize, #[case] packet_loss: f64) {
+    let conditions = NetworkConditions {
+        latency,
+        packet_loss,
+    };
+    // player 0 steps onto the exit while player 1 keeps the peers predicting
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        let action = match frame - STAIRS_FRAME {
+            1..=5 => PlayerAction::Move(MoveDirection::Right),
+            _ => PlayerAction::None,
+        };
+        inputs[0] = action.into();
+    }
+    let mut harness = TwoPeerHarness::with_script(conditions, script);
+    for peer in 0..2 {
+        harness.peer_mut(peer).app_mut().add_systems(
+            AdvanceWorld,
+            put_player_by_exit::<1>.in_set(AdvanceWorldSet::First),
+        );
+    }
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    for peer in 0..2 {
+        let dungeon = harness.peer_mut(peer).world().resource::<DungeonMap>();
+        assert_eq!(dungeon.level, 2, "Peer {peer} did not descend");
+    }
+}
+
+#[test]
+fn mispredicted_descent_rolled_back() {
+    let conditions = NetworkConditions {
+        latency: 6,
+        packet_loss: 0.,
+    };
+    // player 0 steps next to the exit and lets go just before the next step,
+    // which the other peer predicts onto the exit
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        let action = match frame - STAIRS_FRAME {
+            1..=6 => PlayerAction::Move(MoveDirection::Right),
+            _ => PlayerAction::None,
+        };
+        inputs[0] = action.into();
+    }
+    let mut harness = TwoPeerHarness::with_script(conditions, script);
+    for peer in 0..2 {
+        harness.peer_mut(peer).app_mut().add_systems(
+            AdvanceWorld,
+            put_player_by_exit::<2>.in_set(AdvanceWorldSet::First),
+        );
+    }
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    for peer in 0..2 {
+        let dungeon = harness.peer_mut(peer).world().resource::<DungeonMap>();
+        assert_eq!(dungeon.level, 1, "Peer {peer} descended");
+    }
+}
+
+#[rstest]
+#[case::latency(3, 0.)]
+#[case::latency_and_packet_loss(2, 0.1)]
+fn peers_win_in_sync(#[case] latency: usize, #[case] packet_loss: f64) {
+    let conditions = NetworkConditions {
+        latency,
+        packet_loss,
+    };
+    // player 0 grabs the amulet while player 1 keeps the peers predicting
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        let action = match frame - AMULET_FRAME {
+            1..=3 => PlayerAction::GrabItem,
+            _ => PlayerAction::None,
+        };
+        inputs[0] = action.into();
+    }
+    let mut harness = TwoPeerHarness::with_script(conditions, script);
+    for peer in 0..2 {
+        harness.peer_mut(peer).app_mut().add_systems(
+            AdvanceWorld,
+            put_amulet_under_player.in_set(AdvanceWorldSet::First),
+        );
+    }
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    for peer in 0..2 {
+        let app = harness.peer_mut(peer);
+        assert_eq!(app.state(), GameState::GameWon, "Peer {peer} did not win");
+        assert_eq!(app.world().resource::<Winner>().player_id, 0);
+    }
+}
+
+#[test]
+fn mispredicted_win_rolled_back() {
+    let conditions = NetworkConditions {
+        latency: 6,
+        packet_loss: 0.,
+    };
+    // player 0 holds grab and lets go just before the amulet appears under it,
+    // so the other peer predicts a grab
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        let action = match AMULET_FRAME - frame {
+            3..=8 => PlayerAction::GrabItem,
+            _ => PlayerAction::None,
+        };
+        inputs[0] = action.into();
+    }
+    let mut harness = TwoPeerHarness::with_script(conditions, script);
+    for peer in 0..2 {
+        harness.peer_mut(peer).app_mut().add_systems(
+            AdvanceWorld,
+            put_amulet_under_player.in_set(AdvanceWorldSet::First),
+        );
+    }
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    for peer in 0..2 {
+        let app = harness.peer_mut(peer);
+        assert_eq!(app.state(), GameState::InGame, "Peer {peer} left the game");
+        assert!(app.world().get_resource::<Winner>().is_none());
+    }
+}
+
+#[rstest]
+#[case::latency(3, 0.)]
+#[case::latency_and_packet_loss(2, 0.1)]
+fn peers_game_over_in_sync(#[case] latency: usize, #[case] packet_loss: f64) {
+    let conditions = NetworkConditions {
+        latency,
+        packet_loss,
+    };
+    let mut harness = TwoPeerHarness::with_script(conditions, kill_script(false));
+    for peer in 0..2 {
+        harness.peer_mut(peer).app_mut().add_systems(
+            AdvanceWorld,
+            put_monster_by_player.in_set(AdvanceWorldSet::First),
+        );
+    }
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    let reports = [0, 1].map(|peer| {
+        let app = harness.peer_mut(peer);
+        assert_eq!(app.state(), GameState::GameOver, "Peer {peer} not over");
+        *app.world().resource::<GameOverReport>()
+    });
+    assert_eq!(reports[0], reports[1]);
+    assert_eq!(reports[0].player_id, 0);
+}
+
+#[rstest]
+#[case::latency(3, 0.)]
+#[case::latency_and_packet_loss(2, 0.1)]
+fn peers_restart_from_game_over_in_sync(#[case] latency: usize, #[case] packet_loss: f64) {
+    let conditions = NetworkConditions {
+        latency,
+        packet_loss,
+    };
+    let mut harness = TwoPeerHarness::with_script(conditions, kill_script(true));
+    for peer in 0..2 {
+        harness.peer_mut(peer).app_mut().add_systems(
+            AdvanceWorld,
+            put_monster_by_player.in_set(AdvanceWorldSet::First),
+        );
+    }
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    assert_same_dungeon(&mut harness);
+    for peer in 0..2 {
+        let app = harness.peer_mut(peer);
+        assert_eq!(
+            app.state(),
+            GameState::InGame,
+            "Peer {peer} did not restart"
+        );
+        assert_ne!(app.world().resource::<RandomGenerator>().seed, SEED);
+        assert!(app.world().get_resource::<GameOverReport>().is_none());
+        let players = app.world_mut().query::<&Player>().iter(app.world()).count();
+        assert_eq!(players, 2, "Peer {peer} has leftover players");
+    }
+}
+
+#[rstest]
+#[case::latency(3, 0.)]
+#[case::latency_and_packet_loss(2, 0.1)]
+fn peers_restart_in_sync(#[case] latency: usize, #[case] packet_loss: f64) {
+    let conditions = NetworkConditions {
+        latency,
+        packet_loss,
+    };
+    // player 0 pauses and player 1 restarts from the pause menu
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        if frame == PAUSE_FRAME {
+            inputs[0] = PlayerAction::Pause.into();
+        }
+        if frame == RESTART_FRAME {
+            inputs[1] = PlayerAction::Restart.into();
+        }
+    }
+    let mut harness = TwoPeerHarness::with_script(conditions, script);
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    assert_same_dungeon(&mut harness);
+    for peer in 0..2 {
+        let app = harness.peer_mut(peer);
+        assert_ne!(
+            app.world().resource::<RandomGenerator>().seed,
+            SEED,
+            "Peer {peer} did not restart"
+        );
+        assert!(!app.world().resource::<Pause>().is_paused());
+        let players = app.world_mut().query::<&Player>().iter(app.world()).count();
+        assert_eq!(players, 2, "Peer {peer} has leftover players");
+    }
+}
+
+#[rstest]
+#[case::latency(4, 0.)]
+#[case::latency_and_packet_loss(2, 0.1)]
+fn restart_survives_resume(#[case] latency: usize, #[case] packet_loss: f64) {
+    let conditions = NetworkConditions {
+        latency,
+        packet_loss,
+    };
+    // player 1 restarts from the pause menu and player 0 resumes right after
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        if frame == PAUSE_FRAME || frame == RESTART_FRAME + 2 {
+            inputs[0] = PlayerAction::Pause.into();
+        }
+        if frame == RESTART_FRAME {
+            inputs[1] = PlayerAction::Restart.into();
+        }
+    }
+    let mut harness = TwoPeerHarness::with_script(conditions, script);
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    assert_same_dungeon(&mut harness);
+    for peer in 0..2 {
+        let app = harness.peer_mut(peer);
+        assert_ne!(
+            app.world().resource::<RandomGenerator>().seed,
+            SEED,
+            "Peer {peer} did not restart"
+        );
+        assert!(!app.world().resource::<Pause>().is_paused());
+    }
+}
+
+#[test]
+fn mispredicted_restart_rolled_back() {
+    let conditions = NetworkConditions {
+        latency: 6,
+        packet_loss: 0.,
+    };
+    // player 1 holds restart until just before player 0 pauses, so the other
+    // peer predicts a restart from the pause menu, then player 0 resumes
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        if frame == PAUSE_FRAME || frame == RESTART_FRAME {
+            inputs[0] = PlayerAction::Pause.into();
+        }
+        if (PAUSE_FRAME - 8..PAUSE_FRAME).contains(&frame) {
+            inputs[1] = PlayerAction::Restart.into();
+        }
+    }
+    let mut harness = TwoPeerHarness::with_script(conditions, script);
+
+    harness
+        .run(FRAMES)
+        .unwrap_or_else(|desync| panic!("{desync}"));
+
+    assert_same_dungeon(&mut harness);
+    for peer in 0..2 {
+        let app = harness.peer_mut(peer);
+        assert_eq!(
+            app.world().resource::<RandomGenerator>().seed,
+            SEED,
+            "Peer {peer} restarted"
+        );
+        assert!(!app.world().resource::<Pause>().is_paused());
+    }
+}
+
+#[test]
+fn reports_divergent_component() {
+    let mut harness = TwoPeerHarness::new(SEED, NetworkConditions::default(), FRAMES as usize);
+    harness.peer_mut(1).app_mut().add_systems(
+        AdvanceWorld,
+        toughen_monsters.in_set(AdvanceWorldSet::First),
+    );
+
+    let desync = harness.run(FRAMES).expect_err("Peers did not diverge");
+
+    assert_eq!(desync.component, Some("Health"));
+}
+
+/// Player 0 waits for the monster while player 1 keeps the peers predicting,
+/// then holds restart for a while (if restarting) once the game is over.
+fn kill_script(restart: bool) -> Replay {
+    let mut script = random_script(SEED, FRAMES as usize);
+    for (frame, inputs) in (1..).zip(script.inputs.iter_mut()) {
+        inputs[0] = PlayerAction::None.into();
+        if restart && (RESTART_GAME_OVER_FRAME..RESTART_GAME_OVER_FRAME + 5).contains(&frame) {
+            inputs[1] = PlayerAction::Restart.into();
+        }
+    }
+
+    script
+}
+
+/// Put the monster at the lowest position next to player 0 and leave player 0
+/// with 1 hp, on both peers and as part of the frame so it is repeated when the
+/// frame is re-simulated.
+fn put_monster_by_player(
+    dungeon: Res<DungeonMap>,
+    frame: Res<RollbackFrameCount>,
+    mut fov_events: EventWriter<RecalculateFovEvent>,
+    mut monsters: Query<(Entity, &mut Transform), With<Monster>>,
+    mut players: Query<(&Player, &Transform, &mut Health), Without<Monster>>,
+) {
+    if frame.0 != KILL_FRAME {
+        return;
+    }
+
+    let (_, player_transform, mut health) = players
+        .iter_mut()
+        .find(|(player, ..)| player.id == 0)
+        .expect("Player 0 not spawned");
+    health.current = 1;
+    let player_pos = DungeonPosition::from_vec3(player_transform.translation);
+    let occupied = monsters
+        .iter()
+        .map(|(_, transform)| DungeonPosition::from_vec3(transform.translation))
+        .collect::<Vec<_>>();
+    let pos = [(0, 1), (0, -1), (-1, 0), (1, 0)]
+        .into_iter()
+        .map(|(x, y)| DungeonPosition::new(player_pos.x + x, player_pos.y + y))
+        .find(|pos| dungeon.is_walkable(pos) && !occupied.contains(pos))
+        .expect("Player 0 is boxed in");
+
+    let (monster, mut transform) = monsters
+        .iter_mut()
+        .min_by_key(|(_, transform)| transform.translation.truncate().as_ivec2().to_array())
+        .expect("No monsters spawned");
+    transform.translation = pos.to_vec3(transform.translation.z);
+    fov_events.send(RecalculateFovEvent::new(monster, pos.to_vec2().as_ivec2()));
+}
+
+/// Spawn the Amulet of Yala under player 0, on both peers and as part of the
+/// frame so it is repeated when the frame is re-simulated.
+fn put_amulet_under_player(
+    frame: Res<RollbackFrameCount>,
+    mut commands: Commands,
+    players: Query<(&Player, &Transform)>,
+) {
+    if frame.0 != AMULET_FRAME {
+        return;
+    }
+
+    let (_, transform) = players
+        .iter()
+        .find(|(player, _)| player.id == 0)
+        .expect("Player 0 not spawned");
+    let pos = transform.translation.truncate();
+    commands
+        .spawn((
+            MagicItem::Amulet,
+            Grabbable,
+            Transform::from_translation(pos.extend(config::ITEM_Z_LAYER)),
+            InGameEntity,
+        ))
+        .add_rollback();
+}
+
+/// The dungeon is not rollback state, so it is not compared by the harness.
+fn assert_same_dungeon(harness: &mut TwoPeerHarness) {
+    let mut dungeon = |peer| {
+        let dungeon = harness.peer_mut(peer).world().resource::<DungeonMap>();
+        dungeon
+            .tiles()
+            .map(|tile| tile.pos)
+            .filter(|pos| dungeon.is_walkable(pos))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(dungeon(0), dungeon(1), "Peers have different dungeons");
+}
+
+/// Nondeterminism on one peer only
+fn toughen_monsters(mut monsters: Query<&mut Health, With<Monster>>) {
+    monsters.iter_mut().for_each(|mut health| health.max = 99);
+}
+
+/// Put player 0 the given number of tiles left of the exit, on both peers and
+/// as part of the frame so it is repeated when the frame is re-simulated.
+fn put_player_by_exit<const TILES: isize>(
+    dungeon: Res<DungeonMap>,
+    frame: Res<RollbackFrameCount>,
+    mut players: Query<(&Player, &mut Transform)>,
+) {
+    if frame.0 != STAIRS_FRAME {
+        return;
+    }
+
+    let (_, mut transform) = players
+        .iter_mut()
+        .find(|(player, _)| player.id == 0)
+        .expect("Player 0 not spawned");
+    let pos = DungeonPosition::new(dungeon.center.x - TILES, dungeon.center.y);
+    transform.translation = pos.to_vec3(transform.translation.z);
+}